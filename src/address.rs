@@ -1,6 +1,9 @@
 //! Device Address
 
+use core::{convert::TryFrom, fmt, str::FromStr};
+
 const DEFAULT_ADDRESS: u8 = 0b1_1000;
+const PINS_MASK: u8 = 0b111;
 
 /// I2C device address
 ///
@@ -26,6 +29,11 @@ const DEFAULT_ADDRESS: u8 = 0b1_1000;
 /// assert_eq!(0b1_1101u8, mcp9808.address());
 /// ```
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "u8", try_from = "u8")
+)]
 pub enum SlaveAddress {
     /// Default slave address 0b1_1000 - all pins are disconnected
     Default,
@@ -46,9 +54,69 @@ impl From<SlaveAddress> for u8 {
     }
 }
 
+impl SlaveAddress {
+    /// Every address selectable with the address pins, 0x18 to 0x1f
+    pub fn all() -> impl Iterator<Item = SlaveAddress> {
+        (0..=PINS_MASK).map(|pins| SlaveAddress::from_pins(DEFAULT_ADDRESS | pins))
+    }
+
+    /// Address with pins A2, A1, A0 taken from the least significant bits of `address`
+    fn from_pins(address: u8) -> Self {
+        if address & PINS_MASK == 0 {
+            return SlaveAddress::Default;
+        }
+        SlaveAddress::Alternative {
+            a2: address & 0b100 != 0,
+            a1: address & 0b010 != 0,
+            a0: address & 0b001 != 0,
+        }
+    }
+}
+
+/// Address is not one of the MCP9808 addresses 0x18 to 0x1f
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct InvalidAddressError;
+
+impl fmt::Display for InvalidAddressError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid address, expected 0x18 to 0x1f")
+    }
+}
+
+impl TryFrom<u8> for SlaveAddress {
+    type Error = InvalidAddressError;
+
+    fn try_from(address: u8) -> Result<Self, Self::Error> {
+        if address & !PINS_MASK == DEFAULT_ADDRESS {
+            Ok(SlaveAddress::from_pins(address))
+        } else {
+            Err(InvalidAddressError)
+        }
+    }
+}
+
+impl FromStr for SlaveAddress {
+    type Err = InvalidAddressError;
+
+    /// Parses hexadecimal address `0x19` or `19`
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let digits = text.strip_prefix("0x").unwrap_or(text);
+        u8::from_str_radix(digits, 16)
+            .map_err(|_| InvalidAddressError)
+            .and_then(SlaveAddress::try_from)
+    }
+}
+
 impl PartialEq for SlaveAddress {
     fn eq(&self, other: &Self) -> bool {
         let (lhs, rhs): (u8, u8) = ((*self).into(), (*other).into());
         lhs == rhs
     }
 }
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for SlaveAddress {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "{=u8:#04x}", u8::from(*self))
+    }
+}
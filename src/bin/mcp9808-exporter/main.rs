@@ -0,0 +1,452 @@
+//! MCP9808 Prometheus exporter
+//!
+//! Polls the MCP9808 sensors listed in a configuration file
+//! and serves their readings at `/metrics` in the Prometheus text format.
+
+use embedded_hal::blocking::i2c;
+use linux_embedded_hal::I2cdev;
+use mcp9808::{
+    fault::{Fault, FaultyI2c, Trigger},
+    resolution::Resolution,
+    simulator::Simulator,
+    temperature::{Celsius, Millicelsius, TemperatureMeasurement},
+    InvalidAddressError, SlaveAddress, MCP9808,
+};
+use std::{
+    env, fmt,
+    fmt::Write as _,
+    fs,
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    process,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+const USAGE: &str = "\
+Usage: mcp9808-exporter [--config <PATH>]
+
+Options:
+    --config <PATH>     Configuration file [default: /etc/mcp9808-exporter.conf]
+
+Configuration:
+    listen = 0.0.0.0:9808       Address serving /metrics
+    interval = 5000             Poll interval in milliseconds
+
+    [sensor <NAME>]             One section per sensor
+    device = /dev/i2c-1         I2C bus device, or `simulator` for a simulated sensor
+    address = 0x18              Sensor address, 0x18 to 0x1f
+    temperature = 25            Ambient temperature of a simulated sensor
+    fault = nack                Fail every transaction of a simulated sensor
+";
+
+const DEFAULT_CONFIG: &str = "/etc/mcp9808-exporter.conf";
+
+/// Exporter configuration
+struct Config {
+    listen: String,
+    interval: Duration,
+    sensors: Vec<SensorConfig>,
+}
+
+/// `[sensor <NAME>]` section
+struct SensorConfig {
+    name: String,
+    device: String,
+    address: SlaveAddress,
+    temperature: Millicelsius,
+    nack: bool,
+}
+
+/// I2C bus device or simulated sensor
+enum Bus {
+    Linux(I2cdev),
+    Simulated(FaultyI2c<Simulator>),
+}
+
+/// Bus error, formatted for logging
+#[derive(Debug)]
+struct BusError(String);
+
+impl i2c::Write for Bus {
+    type Error = BusError;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        match self {
+            Bus::Linux(i2c) => i2c.write(address, bytes).map_err(bus_error),
+            Bus::Simulated(simulator) => simulator.write(address, bytes).map_err(bus_error),
+        }
+    }
+}
+
+impl i2c::WriteRead for Bus {
+    type Error = BusError;
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        match self {
+            Bus::Linux(i2c) => i2c.write_read(address, bytes, buffer).map_err(bus_error),
+            Bus::Simulated(simulator) => simulator
+                .write_read(address, bytes, buffer)
+                .map_err(bus_error),
+        }
+    }
+}
+
+/// Readings of one poll
+#[derive(Clone, Copy)]
+struct Reading {
+    measurement: TemperatureMeasurement<Millicelsius>,
+    upper: Millicelsius,
+    lower: Millicelsius,
+    critical: Millicelsius,
+    resolution: Resolution,
+}
+
+/// Latest state of a sensor, shared between the poller and the server
+struct SensorState {
+    name: String,
+    address: u8,
+    reading: Option<Reading>,
+    polls: u64,
+    errors: u64,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(error) = run(&args) {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let path = match args {
+        [] => DEFAULT_CONFIG,
+        [option, path] if option == "--config" => path,
+        [help] if help == "--help" || help == "help" => {
+            print!("{}", USAGE);
+            return Ok(());
+        }
+        _ => {
+            return Err(format!(
+                "invalid arguments: {}\n\n{}",
+                args.join(" "),
+                USAGE
+            ))
+        }
+    };
+    let text = fs::read_to_string(path).map_err(|error| format!("{}: {}", path, error))?;
+    let config = parse_config(&text)?;
+
+    let mut sensors = Vec::new();
+    for sensor in &config.sensors {
+        let bus = if sensor.device == "simulator" {
+            let mut simulator = Simulator::new(sensor.address);
+            simulator.set_ambient_temperature(sensor.temperature);
+            let trigger = if sensor.nack {
+                Trigger::EveryNth(1)
+            } else {
+                Trigger::Never
+            };
+            Bus::Simulated(FaultyI2c::new(simulator, Fault::Nack, trigger))
+        } else {
+            Bus::Linux(
+                I2cdev::new(&sensor.device)
+                    .map_err(|error| format!("{}: {}", sensor.device, error))?,
+            )
+        };
+        sensors.push(MCP9808::new(bus, sensor.address));
+    }
+    let states = Arc::new(Mutex::new(
+        config
+            .sensors
+            .iter()
+            .map(|sensor| SensorState {
+                name: sensor.name.clone(),
+                address: sensor.address.into(),
+                reading: None,
+                polls: 0,
+                errors: 0,
+            })
+            .collect::<Vec<_>>(),
+    ));
+
+    // First poll before serving, so the first scrape has readings
+    poll(&mut sensors, &states);
+    let poller_states = Arc::clone(&states);
+    let interval = config.interval;
+    thread::spawn(move || loop {
+        thread::sleep(interval);
+        poll(&mut sensors, &poller_states);
+    });
+
+    let listener = TcpListener::bind(&config.listen)
+        .map_err(|error| format!("{}: {}", config.listen, error))?;
+    let address = listener.local_addr().map_err(|error| error.to_string())?;
+    println!("listening on http://{}/metrics", address);
+    io::stdout().flush().map_err(|error| error.to_string())?;
+
+    for stream in listener.incoming() {
+        let result = stream.and_then(|stream| serve(stream, &states));
+        if let Err(error) = result {
+            eprintln!("warning: {}", error);
+        }
+    }
+    Ok(())
+}
+
+fn poll(sensors: &mut [MCP9808<Bus>], states: &Mutex<Vec<SensorState>>) {
+    for (index, mcp9808) in sensors.iter_mut().enumerate() {
+        let reading = read(mcp9808);
+        let mut states = states.lock().unwrap();
+        let state = &mut states[index];
+        state.polls += 1;
+        match reading {
+            Ok(reading) => state.reading = Some(reading),
+            Err(BusError(error)) => {
+                state.errors += 1;
+                state.reading = None;
+                eprintln!("warning: sensor {}: {}", state.name, error);
+            }
+        }
+    }
+}
+
+fn read(mcp9808: &mut MCP9808<Bus>) -> Result<Reading, BusError> {
+    Ok(Reading {
+        measurement: mcp9808.read_ambient_temperature()?,
+        upper: mcp9808.read_upper_temperature()?,
+        lower: mcp9808.read_lower_temperature()?,
+        critical: mcp9808.read_critical_temperature()?,
+        resolution: mcp9808.read_resolution()?,
+    })
+}
+
+/// Answer one HTTP request, only `GET /metrics` is served
+fn serve(stream: TcpStream, states: &Mutex<Vec<SensorState>>) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Skip headers, the request has no body
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && header.trim_end() != "" {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", render(&states.lock().unwrap())),
+        (Some("GET"), Some(_)) => ("404 Not Found", "not found\n".into()),
+        _ => ("405 Method Not Allowed", "method not allowed\n".into()),
+    };
+    write!(
+        &stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+/// Metrics in the Prometheus text exposition format
+fn render(states: &[SensorState]) -> String {
+    let mut metrics = Metrics::default();
+
+    metrics.family("mcp9808_up", "gauge", "Last poll of the sensor succeeded");
+    for state in states {
+        metrics.sample(state, "mcp9808_up", "", state.reading.is_some() as u8);
+    }
+    metrics.family("mcp9808_polls_total", "counter", "Polls of the sensor");
+    for state in states {
+        metrics.sample(state, "mcp9808_polls_total", "", state.polls);
+    }
+    metrics.family(
+        "mcp9808_read_errors_total",
+        "counter",
+        "Polls of the sensor failed by a bus error",
+    );
+    for state in states {
+        metrics.sample(state, "mcp9808_read_errors_total", "", state.errors);
+    }
+
+    let readings: Vec<(&SensorState, Reading)> = states
+        .iter()
+        .filter_map(|state| state.reading.map(|reading| (state, reading)))
+        .collect();
+    metrics.family(
+        "mcp9808_temperature_celsius",
+        "gauge",
+        "Ambient temperature",
+    );
+    for (state, reading) in &readings {
+        let temperature = celsius(reading.measurement.temperature);
+        metrics.sample(state, "mcp9808_temperature_celsius", "", temperature);
+    }
+    metrics.family(
+        "mcp9808_alert",
+        "gauge",
+        "Ambient temperature is beyond the limit",
+    );
+    for (state, reading) in &readings {
+        let measurement = reading.measurement;
+        for (limit, alert) in &[
+            ("critical", measurement.is_critical),
+            ("upper", measurement.is_upper),
+            ("lower", measurement.is_lower),
+        ] {
+            let label = format!(",limit=\"{}\"", limit);
+            metrics.sample(state, "mcp9808_alert", &label, *alert as u8);
+        }
+    }
+    metrics.family("mcp9808_limit_celsius", "gauge", "Alert limit temperature");
+    for (state, reading) in &readings {
+        for (limit, temperature) in &[
+            ("critical", reading.critical),
+            ("upper", reading.upper),
+            ("lower", reading.lower),
+        ] {
+            let label = format!(",limit=\"{}\"", limit);
+            metrics.sample(
+                state,
+                "mcp9808_limit_celsius",
+                &label,
+                celsius(*temperature),
+            );
+        }
+    }
+    metrics.family(
+        "mcp9808_resolution_celsius",
+        "gauge",
+        "Temperature measurement resolution",
+    );
+    for (state, reading) in &readings {
+        metrics.sample(state, "mcp9808_resolution_celsius", "", reading.resolution);
+    }
+
+    metrics.0
+}
+
+/// Exposition text under construction
+#[derive(Default)]
+struct Metrics(String);
+
+impl Metrics {
+    fn family(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.0, "# HELP {} {}", name, help);
+        let _ = writeln!(self.0, "# TYPE {} {}", name, kind);
+    }
+
+    fn sample<V>(&mut self, state: &SensorState, name: &str, labels: &str, value: V)
+    where
+        V: fmt::Display,
+    {
+        let _ = writeln!(
+            self.0,
+            "{}{{sensor=\"{}\",address=\"{:#04x}\"{}}} {}",
+            name,
+            escape(&state.name),
+            state.address,
+            labels,
+            value
+        );
+    }
+}
+
+fn parse_config(text: &str) -> Result<Config, String> {
+    let mut config = Config {
+        listen: "0.0.0.0:9808".into(),
+        interval: Duration::from_millis(5_000),
+        sensors: Vec::new(),
+    };
+    for (index, line) in text.lines().enumerate() {
+        let invalid = |message: String| format!("line {}: {}", index + 1, message);
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(section) = line.strip_prefix('[') {
+            let name = section
+                .strip_suffix(']')
+                .and_then(|section| section.strip_prefix("sensor "))
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .ok_or_else(|| invalid(format!("invalid section {}", line)))?;
+            if config.sensors.iter().any(|sensor| sensor.name == name) {
+                return Err(invalid(format!("duplicate sensor {}", name)));
+            }
+            config.sensors.push(SensorConfig {
+                name: name.into(),
+                device: "/dev/i2c-1".into(),
+                address: SlaveAddress::Default,
+                temperature: Millicelsius(25_000),
+                nack: false,
+            });
+            continue;
+        }
+
+        let mut parts = line.splitn(2, '=');
+        let (key, value) = match (parts.next(), parts.next()) {
+            (Some(key), Some(value)) => (key.trim(), value.trim()),
+            _ => return Err(invalid(format!("expected KEY = VALUE, got {}", line))),
+        };
+        match (config.sensors.last_mut(), key) {
+            (None, "listen") => config.listen = value.into(),
+            (None, "interval") => {
+                let interval = value
+                    .parse()
+                    .map_err(|_| invalid(format!("invalid interval {}", value)))?;
+                config.interval = Duration::from_millis(interval);
+            }
+            (Some(sensor), "device") => sensor.device = value.into(),
+            (Some(sensor), "address") => {
+                sensor.address = value
+                    .parse()
+                    .map_err(|error: InvalidAddressError| invalid(error.to_string()))?
+            }
+            (Some(sensor), "temperature") => {
+                sensor.temperature = format!("{} °C", value)
+                    .parse()
+                    .map_err(|_| invalid(format!("invalid temperature {}", value)))?
+            }
+            (Some(sensor), "fault") => {
+                sensor.nack = match value {
+                    "none" => false,
+                    "nack" => true,
+                    _ => return Err(invalid(format!("invalid fault {}", value))),
+                }
+            }
+            _ => return Err(invalid(format!("unknown key {}", key))),
+        }
+    }
+    if config.sensors.is_empty() {
+        return Err("no sensor configured".into());
+    }
+    Ok(config)
+}
+
+fn celsius(temperature: Millicelsius) -> f32 {
+    Celsius::from(temperature).0
+}
+
+/// Label value with backslash, quote and newline escaped
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn bus_error<Err>(error: Err) -> BusError
+where
+    Err: fmt::Debug,
+{
+    BusError(format!("bus error: {:?}", error))
+}
@@ -0,0 +1,675 @@
+//! MCP9808 command-line tool
+//!
+//! Reads and configures MCP9808 sensors on a Linux I2C bus device,
+//! or on a simulated sensor with `--simulate`.
+
+use embedded_hal::blocking::i2c;
+use i2c_interface::generic_array::typenum::consts::U5;
+use linux_embedded_hal::I2cdev;
+use mcp9808::{
+    configuration::{
+        AlertOutputControl, AlertOutputMode, AlertOutputPolarity, AlertOutputSelect, Configuration,
+        CriticalTemperatureLock, Hysteresis, InterruptClear, ShutdownMode, TemperatureWindowLock,
+    },
+    datalog::{self, Format, LogReader, LogWriter, Record, RotatingLog, Rotation},
+    filter::{Filter, Median, MovingAverage},
+    resolution::{ParseResolutionError, Resolution},
+    simulator::Simulator,
+    statistics::TemperatureStats,
+    temperature::{Celsius, Millicelsius, TemperatureMeasurement},
+    verify, InvalidAddressError, SlaveAddress, MCP9808,
+};
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt::Debug,
+    fs::File,
+    io::{self, BufReader, Write},
+    process, thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const USAGE: &str = "\
+Usage: mcp9808 [OPTIONS] <COMMAND>
+
+Options:
+    --device <PATH>         I2C bus device [default: /dev/i2c-1]
+    --address <ADDRESS>     Sensor address, 0x18 to 0x1f [default: 0x18]
+    --simulate              Use a simulated sensor instead of the I2C bus device
+    --temperature <C>       Ambient temperature of the simulated sensor [default: 25]
+
+Commands:
+    read [--unit <UNIT>]                    Read ambient temperature and alert flags
+    watch [--unit <UNIT>] [--interval <MS>] [--count <N>]
+                                            Read ambient temperature repeatedly
+    config get                              Print configuration
+    config set <KEY=VALUE>...               Change configuration
+    limits get                              Print T_UPPER, T_LOWER and T_CRIT
+    limits set [upper=<C>] [lower=<C>] [critical=<C>]
+                                            Change limits
+    resolution [0.5|0.25|0.125|0.0625]      Print or change resolution
+    info                                    Print manufacturer and device IDs
+    scan                                    List responding sensor addresses
+    log [--format <FORMAT>] [--unit <UNIT>] [--interval <MS>] [--count <N>] [--sensors <LIST>]
+        [--output <PATH>] [--rotate-size <BYTES>] [--rotate-time <S>]
+                                            Log timestamped measurements to stdout or files
+    stats [--filter median|average] <PATH>...
+                                            Print statistics of logged measurements
+
+Units: celsius, millicelsius
+Log formats: csv, json
+Sensor lists: comma-separated addresses, e.g. 0x18,0x19 [default: --address]
+Log files are numbered, e.g. --output capture.csv writes capture-0001.csv, capture-0002.csv, ...
+
+Configuration keys:
+    hysteresis=0|1.5|3|6
+    shutdown=on|off
+    critical-lock=on
+    window-lock=on
+    interrupt-clear=on
+    alert-output=on|off
+    alert-select=all|critical
+    alert-polarity=low|high
+    alert-mode=comparator|interrupt
+";
+
+/// Options selecting the sensor
+struct Options {
+    device: String,
+    address: SlaveAddress,
+    simulate: bool,
+    temperature: Millicelsius,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(error) = run(&args) {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let (options, command) = parse_options(args)?;
+    if command.is_empty() || command[0] == "help" {
+        print!("{}", USAGE);
+        return Ok(());
+    }
+    if command[0] == "stats" {
+        return stats(&command[1..]);
+    }
+
+    if options.simulate {
+        let mut simulator = Simulator::new(options.address);
+        simulator.set_ambient_temperature(options.temperature);
+        execute(simulator, options.address, command)
+    } else {
+        let i2c = I2cdev::new(&options.device)
+            .map_err(|error| format!("{}: {}", options.device, error))?;
+        execute(i2c, options.address, command)
+    }
+}
+
+fn parse_options(args: &[String]) -> Result<(Options, &[String]), String> {
+    let mut options = Options {
+        device: "/dev/i2c-1".into(),
+        address: SlaveAddress::Default,
+        simulate: false,
+        temperature: Millicelsius(25_000),
+    };
+    let mut index = 0;
+    while index < args.len() && args[index].starts_with("--") {
+        match args[index].as_str() {
+            "--device" => options.device = value(args, index)?.into(),
+            "--address" => options.address = parse_address(value(args, index)?)?,
+            "--temperature" => options.temperature = parse_celsius(value(args, index)?)?,
+            "--simulate" => {
+                options.simulate = true;
+                index += 1;
+                continue;
+            }
+            option => return Err(format!("unknown option {}", option)),
+        }
+        index += 2;
+    }
+    Ok((options, &args[index..]))
+}
+
+/// Value following the option at `index`
+fn value(args: &[String], index: usize) -> Result<&str, String> {
+    args.get(index + 1)
+        .map(String::as_str)
+        .ok_or_else(|| format!("{} requires a value", args[index]))
+}
+
+fn execute<I2C, Err>(i2c: I2C, address: SlaveAddress, command: &[String]) -> Result<(), String>
+where
+    I2C: i2c::Write<Error = Err> + i2c::WriteRead<Error = Err>,
+    Err: Debug,
+{
+    if command[0] == "scan" {
+        return scan(i2c);
+    }
+    if command[0] == "log" {
+        let arguments: Vec<&str> = command[1..].iter().map(String::as_str).collect();
+        return log(i2c, address, &arguments);
+    }
+
+    let mut mcp9808 = MCP9808::new(i2c, address);
+    // report writes ignored because of lock bits instead of printing stale values
+    mcp9808.enable_write_verification();
+    let arguments: Vec<&str> = command[1..].iter().map(String::as_str).collect();
+    match (command[0].as_str(), arguments.as_slice()) {
+        ("read", arguments) => {
+            let unit = parse_unit(arguments)?;
+            print_measurement(&mut mcp9808, unit)
+        }
+        ("watch", arguments) => watch(&mut mcp9808, arguments),
+        ("config", ["get"]) => {
+            print_configuration(mcp9808.read_configuration().map_err(bus_error)?);
+            Ok(())
+        }
+        ("config", ["set", settings @ ..]) if !settings.is_empty() => {
+            let mut configuration = mcp9808.read_configuration().map_err(bus_error)?;
+            for setting in settings {
+                configuration = set_configuration(configuration, setting)?;
+            }
+            mcp9808
+                .write_configuration(configuration)
+                .map_err(write_error)?;
+            print_configuration(mcp9808.read_configuration().map_err(bus_error)?);
+            Ok(())
+        }
+        ("limits", ["get"]) => print_limits(&mut mcp9808),
+        ("limits", ["set", settings @ ..]) if !settings.is_empty() => {
+            for setting in settings {
+                let (key, value) = split_setting(setting)?;
+                let temperature = parse_celsius(value)?;
+                match key {
+                    "upper" => mcp9808.write_upper_temperature(temperature),
+                    "lower" => mcp9808.write_lower_temperature(temperature),
+                    "critical" => mcp9808.write_critical_temperature(temperature),
+                    _ => return Err(format!("unknown limit {}", key)),
+                }
+                .map_err(write_error)?;
+            }
+            print_limits(&mut mcp9808)
+        }
+        ("resolution", []) => {
+            let resolution = mcp9808.read_resolution().map_err(bus_error)?;
+            println!("{} °C", resolution);
+            Ok(())
+        }
+        ("resolution", [value]) => {
+            let resolution: Resolution = value
+                .parse()
+                .map_err(|error: ParseResolutionError| error.to_string())?;
+            mcp9808.write_resolution(resolution).map_err(write_error)?;
+            println!("{} °C", resolution);
+            Ok(())
+        }
+        ("info", []) => {
+            let manufacturer_id = mcp9808.read_manufacturer_id().map_err(bus_error)?;
+            let (device_id, revision) = mcp9808.read_device_information().map_err(bus_error)?;
+            println!(
+                "manufacturer id: {:#06x}{}",
+                manufacturer_id.0,
+                validity(manufacturer_id.is_valid())
+            );
+            println!(
+                "device id: {:#04x}{}",
+                device_id.0,
+                validity(device_id.is_valid())
+            );
+            println!("revision: {:#04x}", revision.0);
+            Ok(())
+        }
+        _ => Err(format!(
+            "invalid command: {}\n\n{}",
+            command.join(" "),
+            USAGE
+        )),
+    }
+}
+
+/// Temperature unit printed by `read` and `watch`
+#[derive(Clone, Copy)]
+enum Unit {
+    Celsius,
+    Millicelsius,
+}
+
+fn parse_unit(arguments: &[&str]) -> Result<Unit, String> {
+    match arguments {
+        [] => Ok(Unit::Celsius),
+        ["--unit", "celsius"] => Ok(Unit::Celsius),
+        ["--unit", "millicelsius"] => Ok(Unit::Millicelsius),
+        _ => Err(format!("invalid arguments: {}", arguments.join(" "))),
+    }
+}
+
+fn watch<I2C, Err>(mcp9808: &mut MCP9808<I2C>, arguments: &[&str]) -> Result<(), String>
+where
+    I2C: i2c::WriteRead<Error = Err>,
+    Err: Debug,
+{
+    let mut unit = Unit::Celsius;
+    let mut interval = Duration::from_millis(1_000);
+    let mut count = None;
+    for pair in arguments.chunks(2) {
+        match pair {
+            ["--unit", _] => unit = parse_unit(pair)?,
+            ["--interval", value] => interval = Duration::from_millis(parse_number(value)?),
+            ["--count", value] => count = Some(parse_number(value)?),
+            _ => return Err(format!("invalid arguments: {}", arguments.join(" "))),
+        }
+    }
+
+    let mut taken: u64 = 0;
+    loop {
+        print_measurement(mcp9808, unit)?;
+        taken += 1;
+        match count {
+            Some(count) if taken >= count => return Ok(()),
+            _ => thread::sleep(interval),
+        }
+    }
+}
+
+fn print_measurement<I2C, Err>(mcp9808: &mut MCP9808<I2C>, unit: Unit) -> Result<(), String>
+where
+    I2C: i2c::WriteRead<Error = Err>,
+    Err: Debug,
+{
+    let measurement: TemperatureMeasurement<Millicelsius> =
+        mcp9808.read_ambient_temperature().map_err(bus_error)?;
+    println!(
+        "{} critical={} upper={} lower={}",
+        format_temperature(measurement.temperature, unit),
+        measurement.is_critical,
+        measurement.is_upper,
+        measurement.is_lower
+    );
+    Ok(())
+}
+
+fn print_limits<I2C, Err>(mcp9808: &mut MCP9808<I2C>) -> Result<(), String>
+where
+    I2C: i2c::WriteRead<Error = Err>,
+    Err: Debug,
+{
+    let upper = mcp9808.read_upper_temperature().map_err(bus_error)?;
+    let lower = mcp9808.read_lower_temperature().map_err(bus_error)?;
+    let critical = mcp9808.read_critical_temperature().map_err(bus_error)?;
+    println!("upper: {}", format_temperature(upper, Unit::Celsius));
+    println!("lower: {}", format_temperature(lower, Unit::Celsius));
+    println!("critical: {}", format_temperature(critical, Unit::Celsius));
+    Ok(())
+}
+
+fn print_configuration(configuration: Configuration) {
+    println!("hysteresis: {:?}", configuration.hysteresis);
+    println!("shutdown mode: {:?}", configuration.shutdown_mode);
+    println!(
+        "critical temperature lock: {:?}",
+        configuration.critical_temperature_lock
+    );
+    println!(
+        "temperature window lock: {:?}",
+        configuration.temperature_window_lock
+    );
+    println!("interrupt clear: {:?}", configuration.interrupt_clear);
+    println!(
+        "alert output status: {:?}",
+        configuration.alert_output_status
+    );
+    println!(
+        "alert output control: {:?}",
+        configuration.alert_output_control
+    );
+    println!(
+        "alert output select: {:?}",
+        configuration.alert_output_select
+    );
+    println!(
+        "alert output polarity: {:?}",
+        configuration.alert_output_polarity
+    );
+    println!("alert output mode: {:?}", configuration.alert_output_mode);
+}
+
+fn set_configuration(configuration: Configuration, setting: &str) -> Result<Configuration, String> {
+    let (key, value) = split_setting(setting)?;
+    let invalid = || format!("invalid configuration {}", setting);
+    Ok(match (key, value) {
+        ("hysteresis", value) => Configuration {
+            hysteresis: match value {
+                "0" => Hysteresis::Deg0C,
+                "1.5" => Hysteresis::Deg1_5C,
+                "3" => Hysteresis::Deg3_0C,
+                "6" => Hysteresis::Deg6_0C,
+                _ => return Err(invalid()),
+            },
+            ..configuration
+        },
+        ("shutdown", value) => Configuration {
+            shutdown_mode: if parse_switch(value).ok_or_else(invalid)? {
+                ShutdownMode::Shutdown
+            } else {
+                ShutdownMode::ContinuousConversion
+            },
+            ..configuration
+        },
+        ("critical-lock", "on") => Configuration {
+            critical_temperature_lock: CriticalTemperatureLock::Locked,
+            ..configuration
+        },
+        ("window-lock", "on") => Configuration {
+            temperature_window_lock: TemperatureWindowLock::Locked,
+            ..configuration
+        },
+        ("interrupt-clear", "on") => Configuration {
+            interrupt_clear: InterruptClear::Cleared,
+            ..configuration
+        },
+        ("alert-output", value) => Configuration {
+            alert_output_control: if parse_switch(value).ok_or_else(invalid)? {
+                AlertOutputControl::Enabled
+            } else {
+                AlertOutputControl::Disabled
+            },
+            ..configuration
+        },
+        ("alert-select", "all") => Configuration {
+            alert_output_select: AlertOutputSelect::UpperLowerCritical,
+            ..configuration
+        },
+        ("alert-select", "critical") => Configuration {
+            alert_output_select: AlertOutputSelect::CriticalOnly,
+            ..configuration
+        },
+        ("alert-polarity", "low") => Configuration {
+            alert_output_polarity: AlertOutputPolarity::ActiveLow,
+            ..configuration
+        },
+        ("alert-polarity", "high") => Configuration {
+            alert_output_polarity: AlertOutputPolarity::ActiveHigh,
+            ..configuration
+        },
+        ("alert-mode", "comparator") => Configuration {
+            alert_output_mode: AlertOutputMode::Comparator,
+            ..configuration
+        },
+        ("alert-mode", "interrupt") => Configuration {
+            alert_output_mode: AlertOutputMode::Interrupt,
+            ..configuration
+        },
+        _ => return Err(format!("invalid configuration {}", setting)),
+    })
+}
+
+/// Destination of logged records
+enum LogOutput {
+    Stdout(LogWriter<io::Stdout>),
+    Files(RotatingLog),
+}
+
+fn log<I2C, Err>(mut i2c: I2C, address: SlaveAddress, arguments: &[&str]) -> Result<(), String>
+where
+    I2C: i2c::Write<Error = Err> + i2c::WriteRead<Error = Err>,
+    Err: Debug,
+{
+    let mut format = Format::Csv;
+    let mut unit = datalog::Unit::Celsius;
+    let mut interval = Duration::from_millis(1_000);
+    let mut count = None;
+    let mut addresses = vec![address];
+    let mut output = None;
+    let mut rotation = Rotation::default();
+    for pair in arguments.chunks(2) {
+        match pair {
+            ["--format", "csv"] => format = Format::Csv,
+            ["--format", "json"] => format = Format::JsonLines,
+            ["--unit", "celsius"] => unit = datalog::Unit::Celsius,
+            ["--unit", "millicelsius"] => unit = datalog::Unit::Millicelsius,
+            ["--interval", value] => interval = Duration::from_millis(parse_number(value)?),
+            ["--count", value] => count = Some(parse_number(value)?),
+            ["--sensors", value] => {
+                addresses = value
+                    .split(',')
+                    .map(parse_address)
+                    .collect::<Result<_, _>>()?
+            }
+            ["--output", path] => output = Some(*path),
+            ["--rotate-size", value] => rotation.max_bytes = Some(parse_number(value)?),
+            ["--rotate-time", value] => {
+                rotation.max_duration_ms = Some(parse_number::<u64>(value)?.saturating_mul(1_000))
+            }
+            _ => return Err(format!("invalid arguments: {}", arguments.join(" "))),
+        }
+    }
+
+    let mut output = match output {
+        Some(path) => LogOutput::Files(RotatingLog::new(path, format, unit, rotation)),
+        None => LogOutput::Stdout(LogWriter::new(io::stdout(), format, unit)),
+    };
+    let mut taken: u64 = 0;
+    loop {
+        for &address in &addresses {
+            let mut mcp9808 = MCP9808::new(i2c, address);
+            let timestamp_ms = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as u64)
+                .unwrap_or_default();
+            let reading = mcp9808.read_ambient_temperature().and_then(|measurement| {
+                mcp9808
+                    .read_resolution()
+                    .map(|resolution| (measurement, resolution))
+            });
+            i2c = mcp9808.release();
+
+            match reading {
+                Ok((measurement, resolution)) => {
+                    let record = Record {
+                        timestamp_ms,
+                        address: address.into(),
+                        measurement,
+                        resolution,
+                    };
+                    match &mut output {
+                        LogOutput::Stdout(writer) => writer
+                            .write(&record)
+                            .and_then(|_| writer.flush())
+                            .map_err(|error| error.to_string())?,
+                        LogOutput::Files(log) => log
+                            .write(&record)
+                            .and_then(|_| log.flush())
+                            .map_err(log_error)?,
+                    }
+                }
+                Err(error) => {
+                    eprintln!("warning: {:#04x}: {}", u8::from(address), bus_error(error))
+                }
+            }
+        }
+        taken += 1;
+        match count {
+            Some(count) if taken >= count => return Ok(()),
+            _ => thread::sleep(interval),
+        }
+    }
+}
+
+/// Statistics of one sensor in replayed logs
+struct ReplayedSensor {
+    stats: TemperatureStats,
+    filter: Option<Box<dyn Filter>>,
+}
+
+fn stats(arguments: &[String]) -> Result<(), String> {
+    let (filter, paths) = match arguments {
+        [option, filter, paths @ ..] if option == "--filter" => (Some(filter.as_str()), paths),
+        paths => (None, paths),
+    };
+    let new_filter = || -> Result<Option<Box<dyn Filter>>, String> {
+        match filter {
+            None => Ok(None),
+            Some("median") => Ok(Some(Box::new(Median::<U5>::new()))),
+            Some("average") => Ok(Some(Box::new(MovingAverage::<U5>::new()))),
+            Some(filter) => Err(format!("unknown filter {}", filter)),
+        }
+    };
+    new_filter()?;
+    if paths.is_empty() {
+        return Err(format!("stats requires a log file\n\n{}", USAGE));
+    }
+
+    let mut sensors: BTreeMap<u8, ReplayedSensor> = BTreeMap::new();
+    for path in paths {
+        let file = File::open(path).map_err(|error| format!("{}: {}", path, error))?;
+        for record in LogReader::new(BufReader::new(file)) {
+            let record = record.map_err(|error| format!("{}: {}", path, log_error(error)))?;
+            if !sensors.contains_key(&record.address) {
+                let sensor = ReplayedSensor {
+                    stats: TemperatureStats::new(),
+                    filter: new_filter()?,
+                };
+                sensors.insert(record.address, sensor);
+            }
+            let sensor = sensors.get_mut(&record.address).unwrap();
+            let temperature = match &mut sensor.filter {
+                Some(filter) => filter.filter(record.measurement.temperature),
+                None => record.measurement.temperature,
+            };
+            sensor.stats.add(temperature, record.timestamp_ms);
+        }
+    }
+
+    let format = |temperature: Option<Millicelsius>| match temperature {
+        Some(temperature) => format_temperature(temperature, Unit::Celsius),
+        None => "-".into(),
+    };
+    for (address, sensor) in &sensors {
+        let stats = &sensor.stats;
+        println!(
+            "{:#04x}: samples={} min={} max={} mean={} stddev={}",
+            address,
+            stats.count(),
+            format(stats.min().map(|min| min.temperature)),
+            format(stats.max().map(|max| max.temperature)),
+            format(stats.mean()),
+            format(stats.standard_deviation())
+        );
+    }
+    if sensors.is_empty() {
+        println!("no samples");
+    }
+    Ok(())
+}
+
+/// Probe every MCP9808 address for a valid Manufacturer ID
+fn scan<I2C, Err>(mut i2c: I2C) -> Result<(), String>
+where
+    I2C: i2c::WriteRead<Error = Err>,
+{
+    let mut found = false;
+    for address in SlaveAddress::all() {
+        let mut mcp9808 = MCP9808::new(i2c, address);
+        if let Ok(manufacturer_id) = mcp9808.read_manufacturer_id() {
+            found = true;
+            println!(
+                "{:#04x}{}",
+                mcp9808.address(),
+                validity(manufacturer_id.is_valid())
+            );
+        }
+        i2c = mcp9808.release();
+    }
+    if !found {
+        println!("no sensor found");
+    }
+    Ok(())
+}
+
+fn parse_address(value: &str) -> Result<SlaveAddress, String> {
+    value
+        .parse()
+        .map_err(|error: InvalidAddressError| error.to_string())
+}
+
+fn parse_celsius(value: &str) -> Result<Millicelsius, String> {
+    format!("{} °C", value)
+        .parse()
+        .map_err(|_| format!("invalid temperature {}", value))
+}
+
+fn parse_number<T>(value: &str) -> Result<T, String>
+where
+    T: std::str::FromStr,
+{
+    value
+        .parse()
+        .map_err(|_| format!("invalid number {}", value))
+}
+
+fn parse_switch(value: &str) -> Option<bool> {
+    match value {
+        "on" => Some(true),
+        "off" => Some(false),
+        _ => None,
+    }
+}
+
+fn split_setting(setting: &str) -> Result<(&str, &str), String> {
+    let mut parts = setting.splitn(2, '=');
+    match (parts.next(), parts.next()) {
+        (Some(key), Some(value)) => Ok((key, value)),
+        _ => Err(format!("expected KEY=VALUE, got {}", setting)),
+    }
+}
+
+fn format_temperature(temperature: Millicelsius, unit: Unit) -> String {
+    match unit {
+        Unit::Celsius => Celsius::from(temperature).to_string(),
+        Unit::Millicelsius => temperature.to_string(),
+    }
+}
+
+fn validity(valid: bool) -> &'static str {
+    if valid {
+        ""
+    } else {
+        " (unexpected)"
+    }
+}
+
+fn log_error(error: datalog::Error) -> String {
+    match error {
+        datalog::Error::Io(error) => error.to_string(),
+        datalog::Error::Parse { line } => format!("invalid log line {}", line),
+    }
+}
+
+fn bus_error<Err>(error: Err) -> String
+where
+    Err: Debug,
+{
+    format!("bus error: {:?}", error)
+}
+
+fn write_error<Err>(error: verify::Error<Err>) -> String
+where
+    Err: Debug,
+{
+    match error {
+        verify::Error::I2c(error) => bus_error(error),
+        verify::Error::VerifyFailed {
+            register,
+            expected,
+            actual,
+        } => format!(
+            "write to {:?} not applied: wrote {:#06x}, read back {:#06x}",
+            register, expected, actual
+        ),
+    }
+}
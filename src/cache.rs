@@ -0,0 +1,156 @@
+//! Shadow Register Cache
+//!
+//! When enabled, values read from the limit, resolution and ID registers are kept in memory
+//! and later reads of those registers are served without bus traffic.
+//! Writes store the written value as the register holds it. A write the device ignores
+//! because of a lock bit is only noticed with write verification enabled,
+//! which caches the value read back instead.
+//! The ambient temperature and configuration registers are never cached,
+//! as they carry live alert status bits.
+
+use crate::{hal::blocking::i2c, registers::Register, verify, MCP9808};
+use i2c_interface::generic_array::{ArrayLength, GenericArray};
+
+const REGISTER_COUNT: usize = 9;
+
+/// Register cache statistics
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct CacheStats {
+    /// Reads served from the cache
+    pub hits: u32,
+
+    /// Reads of cacheable registers that went to the bus
+    pub misses: u32,
+}
+
+/// Cached register values, disabled by default
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RegisterCache {
+    enabled: bool,
+    registers: [Option<[u8; 2]>; REGISTER_COUNT],
+    stats: CacheStats,
+}
+
+impl<I2C> MCP9808<I2C> {
+    /// Serve reads of registers other than T_A and CONFIG from the register cache
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcp9808::{resolution::Resolution, simulator::Simulator, SlaveAddress, MCP9808};
+    ///
+    /// let mut mcp9808 = MCP9808::new(Simulator::new(SlaveAddress::Default), SlaveAddress::Default);
+    /// mcp9808.enable_cache();
+    ///
+    /// assert_eq!(Ok(Resolution::Deg0_0625C), mcp9808.read_resolution());
+    /// assert_eq!(Ok(Resolution::Deg0_0625C), mcp9808.read_resolution());
+    /// assert_eq!(1, mcp9808.cache_stats().hits);
+    /// assert_eq!(1, mcp9808.cache_stats().misses);
+    /// ```
+    pub fn enable_cache(&mut self) {
+        self.cache.enabled = true;
+    }
+
+    /// Read all registers from the bus and drop cached values
+    pub fn disable_cache(&mut self) {
+        self.cache.enabled = false;
+        self.invalidate_cache();
+    }
+
+    /// Register cache is enabled
+    pub fn is_cache_enabled(&self) -> bool {
+        self.cache.enabled
+    }
+
+    /// Drop cached values, e.g. after the device may have been reset
+    pub fn invalidate_cache(&mut self) {
+        self.cache.registers = [None; REGISTER_COUNT];
+    }
+
+    /// Register cache statistics
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats
+    }
+
+    /// Clear register cache statistics
+    pub fn reset_cache_stats(&mut self) {
+        self.cache.stats = CacheStats::default();
+    }
+
+    /// Read register from the cache or the bus
+    pub(crate) fn read_register<N, Err>(
+        &mut self,
+        register: Register,
+    ) -> Result<GenericArray<u8, N>, Err>
+    where
+        I2C: i2c::WriteRead<Error = Err>,
+        N: ArrayLength<u8>,
+    {
+        let cacheable = self.cache.enabled && is_cacheable(register);
+        let index = register as usize;
+        if cacheable {
+            if let Some(value) = self.cache.registers[index] {
+                self.cache.stats.hits = self.cache.stats.hits.saturating_add(1);
+                return Ok(GenericArray::clone_from_slice(&value[..N::to_usize()]));
+            }
+            self.cache.stats.misses = self.cache.stats.misses.saturating_add(1);
+        }
+
+        let raw: GenericArray<u8, N> = self.i2c_interface.read_register(register)?;
+        if cacheable {
+            let mut value = [0; 2];
+            value[..raw.len()].copy_from_slice(&raw);
+            self.cache.registers[index] = Some(value);
+        }
+        Ok(raw)
+    }
+
+    /// Write register to the bus and verify it when enabled, storing the held value in the cache
+    pub(crate) fn write_register<N, Err>(
+        &mut self,
+        register: Register,
+        raw: GenericArray<u8, N>,
+    ) -> Result<(), verify::Error<Err>>
+    where
+        I2C: i2c::Write<Error = Err> + i2c::WriteRead<Error = Err>,
+        N: ArrayLength<u8>,
+    {
+        let index = register as usize;
+        self.cache.registers[index] = None;
+        self.i2c_interface
+            .write_register(register, raw.clone())
+            .map_err(verify::Error::I2c)?;
+        let held = if self.verify {
+            self.verify_register(register, &raw)?
+        } else {
+            raw
+        };
+
+        if self.cache.enabled && is_cacheable(register) {
+            let mask = verify::register_mask(register).to_be_bytes();
+            let mut value = [0; 2];
+            for ((value, byte), mask) in value.iter_mut().zip(&held).zip(&mask[2 - held.len()..]) {
+                *value = byte & mask;
+            }
+            self.cache.registers[index] = Some(value);
+        }
+        Ok(())
+    }
+
+    /// Read with the register cache bypassed, leaving cached values and statistics untouched
+    pub(crate) fn read_uncached<T, F>(&mut self, read: F) -> T
+    where
+        F: FnOnce(&mut Self) -> T,
+    {
+        let enabled = self.cache.enabled;
+        self.cache.enabled = false;
+        let result = read(self);
+        self.cache.enabled = enabled;
+        result
+    }
+}
+
+/// Register value only changes when written
+fn is_cacheable(register: Register) -> bool {
+    register != Register::AmbientTemperatureRegister && register != Register::ConfigurationRegister
+}
@@ -0,0 +1,375 @@
+//! Temperature Calibration
+
+use crate::{
+    hal::blocking::i2c,
+    temperature::{divide_rounded, Millicelsius, TemperatureMeasurement, TemperatureUnit},
+    verify, MCP9808,
+};
+use core::convert::TryFrom;
+use i2c_interface::generic_array::{ArrayLength, GenericArray};
+
+/// Gain of one in parts per million
+pub const UNITY_GAIN: i32 = 1_000_000;
+
+/// Correction mapping device temperatures to calibrated temperatures
+pub trait Calibration {
+    /// Calibrated temperature for temperature read from the device
+    fn correct(&self, measured: Millicelsius) -> Millicelsius;
+
+    /// Device temperature corresponding to calibrated temperature
+    fn inverse(&self, calibrated: Millicelsius) -> Millicelsius;
+}
+
+/// Calibration error
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Error {
+    /// Gain is zero or negative
+    InvalidGain,
+    /// Calibration points do not strictly increase in both measured and reference temperature
+    Unsorted,
+    /// Fewer than two calibration points
+    NotEnoughPoints,
+    /// Gain or offset from calibration points does not fit in `i32`
+    Overflow,
+}
+
+/// Pair of device temperature and reference thermometer temperature
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct CalibrationPoint {
+    /// Temperature read from the device
+    pub measured: Millicelsius,
+
+    /// Temperature of the reference thermometer
+    pub reference: Millicelsius,
+}
+
+impl CalibrationPoint {
+    /// Creates new calibration point from device and reference temperature `Unit`
+    pub fn new<Unit>(measured: Unit, reference: Unit) -> Self
+    where
+        Unit: Into<Millicelsius>,
+    {
+        CalibrationPoint {
+            measured: measured.into(),
+            reference: reference.into(),
+        }
+    }
+}
+
+/// Linear calibration, `calibrated = measured * gain + offset`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct LinearCalibration {
+    offset: Millicelsius,
+    gain: i32,
+}
+
+impl Default for LinearCalibration {
+    /// Calibration without any correction
+    fn default() -> Self {
+        LinearCalibration {
+            offset: Millicelsius(0),
+            gain: UNITY_GAIN,
+        }
+    }
+}
+
+impl LinearCalibration {
+    /// Creates new calibration with `offset` and `gain` in parts per million
+    pub fn new<Unit>(offset: Unit, gain: i32) -> Result<Self, Error>
+    where
+        Unit: Into<Millicelsius>,
+    {
+        if gain <= 0 {
+            return Err(Error::InvalidGain);
+        }
+        Ok(LinearCalibration {
+            offset: offset.into(),
+            gain,
+        })
+    }
+
+    /// Creates new calibration correcting offset only
+    pub fn from_offset<Unit>(offset: Unit) -> Self
+    where
+        Unit: Into<Millicelsius>,
+    {
+        LinearCalibration {
+            offset: offset.into(),
+            gain: UNITY_GAIN,
+        }
+    }
+
+    /// Creates new calibration from two `CalibrationPoint`s
+    pub fn from_points(first: CalibrationPoint, second: CalibrationPoint) -> Result<Self, Error> {
+        let (first, second) = if first.measured <= second.measured {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        let measured_span = i64::from(second.measured.0) - i64::from(first.measured.0);
+        let reference_span = i64::from(second.reference.0) - i64::from(first.reference.0);
+        if measured_span == 0 || reference_span <= 0 {
+            return Err(Error::Unsorted);
+        }
+
+        let gain = divide_rounded(reference_span * i64::from(UNITY_GAIN), measured_span);
+        let gain = i32::try_from(gain).map_err(|_| Error::Overflow)?;
+        let offset = i64::from(first.reference.0)
+            - divide_rounded(
+                i64::from(first.measured.0) * i64::from(gain),
+                i64::from(UNITY_GAIN),
+            );
+        let offset = i32::try_from(offset).map_err(|_| Error::Overflow)?;
+        LinearCalibration::new(Millicelsius(offset), gain)
+    }
+
+    /// Offset applied after gain
+    pub fn offset(&self) -> Millicelsius {
+        self.offset
+    }
+
+    /// Gain in parts per million
+    pub fn gain(&self) -> i32 {
+        self.gain
+    }
+}
+
+impl Calibration for LinearCalibration {
+    fn correct(&self, measured: Millicelsius) -> Millicelsius {
+        let scaled = divide_rounded(
+            i64::from(measured.0) * i64::from(self.gain),
+            i64::from(UNITY_GAIN),
+        );
+        Millicelsius((scaled + i64::from(self.offset.0)) as i32)
+    }
+
+    fn inverse(&self, calibrated: Millicelsius) -> Millicelsius {
+        let shifted = i64::from(calibrated.0) - i64::from(self.offset.0);
+        Millicelsius(divide_rounded(shifted * i64::from(UNITY_GAIN), i64::from(self.gain)) as i32)
+    }
+}
+
+/// Piecewise-linear calibration through `N` points,
+/// extrapolated with the first and last segment outside of the table
+#[derive(Debug, PartialEq, Clone)]
+pub struct TableCalibration<N>
+where
+    N: ArrayLength<CalibrationPoint>,
+{
+    points: GenericArray<CalibrationPoint, N>,
+}
+
+impl<N> TableCalibration<N>
+where
+    N: ArrayLength<CalibrationPoint>,
+{
+    /// Creates new calibration from points strictly increasing in both temperatures
+    pub fn new(points: GenericArray<CalibrationPoint, N>) -> Result<Self, Error> {
+        if points.len() < 2 {
+            return Err(Error::NotEnoughPoints);
+        }
+        let sorted = points.windows(2).all(|pair| {
+            pair[0].measured < pair[1].measured && pair[0].reference < pair[1].reference
+        });
+        if !sorted {
+            return Err(Error::Unsorted);
+        }
+        Ok(TableCalibration { points })
+    }
+
+    /// Calibration points
+    pub fn points(&self) -> &[CalibrationPoint] {
+        &self.points
+    }
+
+    fn segment<F>(&self, key: F, value: Millicelsius) -> (CalibrationPoint, CalibrationPoint)
+    where
+        F: Fn(&CalibrationPoint) -> Millicelsius,
+    {
+        let last = self.points.len() - 1;
+        let index = self
+            .points
+            .iter()
+            .position(|point| key(point) > value)
+            .unwrap_or(last)
+            .max(1);
+        (self.points[index - 1], self.points[index])
+    }
+}
+
+impl<N> Calibration for TableCalibration<N>
+where
+    N: ArrayLength<CalibrationPoint>,
+{
+    fn correct(&self, measured: Millicelsius) -> Millicelsius {
+        let (lower, upper) = self.segment(|point| point.measured, measured);
+        interpolate(
+            measured,
+            (lower.measured, lower.reference),
+            (upper.measured, upper.reference),
+        )
+    }
+
+    fn inverse(&self, calibrated: Millicelsius) -> Millicelsius {
+        let (lower, upper) = self.segment(|point| point.reference, calibrated);
+        interpolate(
+            calibrated,
+            (lower.reference, lower.measured),
+            (upper.reference, upper.measured),
+        )
+    }
+}
+
+/// `MCP9808` with `Calibration` applied to temperatures read and inverted for limits written
+///
+/// # Example
+///
+/// ```
+/// use mcp9808::{
+///     calibration::{CalibratedSensor, LinearCalibration},
+///     simulator::Simulator,
+///     temperature::{Millicelsius, TemperatureMeasurement},
+///     SlaveAddress, MCP9808,
+/// };
+///
+/// let mut simulator = Simulator::new(SlaveAddress::Default);
+/// simulator.set_ambient_temperature(Millicelsius(25_000));
+///
+/// let mcp9808 = MCP9808::new(simulator, SlaveAddress::Default);
+/// let calibration = LinearCalibration::from_offset(Millicelsius(-250));
+/// let mut sensor = CalibratedSensor::new(mcp9808, calibration);
+/// let measurement: TemperatureMeasurement<Millicelsius> =
+///     sensor.read_ambient_temperature().unwrap();
+/// assert_eq!(Millicelsius(24_750), measurement.temperature);
+/// ```
+pub struct CalibratedSensor<I2C, C> {
+    mcp9808: MCP9808<I2C>,
+    calibration: C,
+}
+
+impl<I2C, C> CalibratedSensor<I2C, C>
+where
+    C: Calibration,
+{
+    /// Creates new calibrated sensor from `MCP9808` and `Calibration`
+    pub fn new(mcp9808: MCP9808<I2C>, calibration: C) -> Self {
+        CalibratedSensor {
+            mcp9808,
+            calibration,
+        }
+    }
+
+    /// Read `TemperatureMeasurement` from `AmbientTemperatureRegister` with calibrated temperature
+    pub fn read_ambient_temperature<Unit, Err>(
+        &mut self,
+    ) -> Result<TemperatureMeasurement<Unit>, Err>
+    where
+        I2C: i2c::WriteRead<Error = Err>,
+        Unit: From<Millicelsius> + TemperatureUnit,
+    {
+        let measurement: TemperatureMeasurement<Millicelsius> =
+            self.mcp9808.read_ambient_temperature()?;
+        Ok(TemperatureMeasurement {
+            temperature: self.calibration.correct(measurement.temperature).into(),
+            is_critical: measurement.is_critical,
+            is_upper: measurement.is_upper,
+            is_lower: measurement.is_lower,
+        })
+    }
+
+    /// Read calibrated Temperature `Unit` from `UpperTemperatureRegister`
+    pub fn read_upper_temperature<Unit, Err>(&mut self) -> Result<Unit, Err>
+    where
+        I2C: i2c::WriteRead<Error = Err>,
+        Unit: From<Millicelsius> + TemperatureUnit,
+    {
+        let temperature = self.mcp9808.read_upper_temperature()?;
+        Ok(self.calibration.correct(temperature).into())
+    }
+
+    /// Write calibrated Temperature `Unit` to `UpperTemperatureRegister`
+    pub fn write_upper_temperature<Unit, Err>(
+        &mut self,
+        temperature: Unit,
+    ) -> Result<(), verify::Error<Err>>
+    where
+        I2C: i2c::Write<Error = Err> + i2c::WriteRead<Error = Err>,
+        Unit: Into<Millicelsius> + TemperatureUnit,
+    {
+        let temperature = self.calibration.inverse(temperature.into());
+        self.mcp9808.write_upper_temperature(temperature)
+    }
+
+    /// Read calibrated Temperature `Unit` from `LowerTemperatureRegister`
+    pub fn read_lower_temperature<Unit, Err>(&mut self) -> Result<Unit, Err>
+    where
+        I2C: i2c::WriteRead<Error = Err>,
+        Unit: From<Millicelsius> + TemperatureUnit,
+    {
+        let temperature = self.mcp9808.read_lower_temperature()?;
+        Ok(self.calibration.correct(temperature).into())
+    }
+
+    /// Write calibrated Temperature `Unit` to `LowerTemperatureRegister`
+    pub fn write_lower_temperature<Unit, Err>(
+        &mut self,
+        temperature: Unit,
+    ) -> Result<(), verify::Error<Err>>
+    where
+        I2C: i2c::Write<Error = Err> + i2c::WriteRead<Error = Err>,
+        Unit: Into<Millicelsius> + TemperatureUnit,
+    {
+        let temperature = self.calibration.inverse(temperature.into());
+        self.mcp9808.write_lower_temperature(temperature)
+    }
+
+    /// Read calibrated Temperature `Unit` from `CriticalTemperatureRegister`
+    pub fn read_critical_temperature<Unit, Err>(&mut self) -> Result<Unit, Err>
+    where
+        I2C: i2c::WriteRead<Error = Err>,
+        Unit: From<Millicelsius> + TemperatureUnit,
+    {
+        let temperature = self.mcp9808.read_critical_temperature()?;
+        Ok(self.calibration.correct(temperature).into())
+    }
+
+    /// Write calibrated Temperature `Unit` to `CriticalTemperatureRegister`
+    pub fn write_critical_temperature<Unit, Err>(
+        &mut self,
+        temperature: Unit,
+    ) -> Result<(), verify::Error<Err>>
+    where
+        I2C: i2c::Write<Error = Err> + i2c::WriteRead<Error = Err>,
+        Unit: Into<Millicelsius> + TemperatureUnit,
+    {
+        let temperature = self.calibration.inverse(temperature.into());
+        self.mcp9808.write_critical_temperature(temperature)
+    }
+
+    /// Applied `Calibration`
+    pub fn calibration(&self) -> &C {
+        &self.calibration
+    }
+
+    /// Underlying `MCP9808`, for access to uncalibrated registers
+    pub fn sensor(&mut self) -> &mut MCP9808<I2C> {
+        &mut self.mcp9808
+    }
+
+    /// Release the `MCP9808` and `Calibration`
+    pub fn release(self) -> (MCP9808<I2C>, C) {
+        (self.mcp9808, self.calibration)
+    }
+}
+
+/// Value at `x` on the line through points `(x0, y0)` and `(x1, y1)`
+fn interpolate(
+    x: Millicelsius,
+    (x0, y0): (Millicelsius, Millicelsius),
+    (x1, y1): (Millicelsius, Millicelsius),
+) -> Millicelsius {
+    let span = i64::from(x1.0) - i64::from(x0.0);
+    let offset = i64::from(x.0) - i64::from(x0.0);
+    let delta = i64::from(y1.0) - i64::from(y0.0);
+    Millicelsius((i64::from(y0.0) + divide_rounded(delta * offset, span)) as i32)
+}
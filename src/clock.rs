@@ -0,0 +1,34 @@
+//! Time Source
+
+/// Monotonic time source used by components that need elapsed time
+///
+/// # Example
+///
+/// ```
+/// use core::cell::Cell;
+/// use mcp9808::clock::Clock;
+///
+/// struct Ticks(Cell<u64>);
+///
+/// impl Clock for Ticks {
+///     fn now_ms(&self) -> u64 {
+///         self.0.get()
+///     }
+/// }
+///
+/// let ticks = Ticks(Cell::new(1_500));
+/// assert_eq!(1_500, (&ticks).now_ms());
+/// ```
+pub trait Clock {
+    /// Milliseconds elapsed since an arbitrary, fixed point in time
+    fn now_ms(&self) -> u64;
+}
+
+impl<C> Clock for &C
+where
+    C: Clock + ?Sized,
+{
+    fn now_ms(&self) -> u64 {
+        (**self).now_ms()
+    }
+}
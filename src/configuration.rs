@@ -3,7 +3,8 @@
 // Clippy warns about `FromPrimitive`, which is not useless
 #![allow(clippy::useless_attribute)]
 
-use crate::{hal::blocking::i2c, registers::Register, MCP9808};
+use crate::{hal::blocking::i2c, registers::Register, verify, MCP9808};
+use core::fmt;
 use i2c_interface::generic_array::{typenum::consts::U2, GenericArray};
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
@@ -14,14 +15,20 @@ type Raw = GenericArray<u8, U2>;
 /// This bit can not be altered when either of the Lock bits are set (bit 6 and bit 7).
 /// This bit can be programmed in Shutdown mode.
 #[derive(Debug, PartialEq, Clone, Copy, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Hysteresis {
     /// 0°C(power-up default)
+    #[cfg_attr(feature = "serde", serde(rename = "0C"))]
     Deg0C = 0b00,
     /// +1.5°C
+    #[cfg_attr(feature = "serde", serde(rename = "1.5C"))]
     Deg1_5C = 0b01,
     /// +3.0°C
+    #[cfg_attr(feature = "serde", serde(rename = "3.0C"))]
     Deg3_0C = 0b10,
     /// +6.0°C
+    #[cfg_attr(feature = "serde", serde(rename = "6.0C"))]
     Deg6_0C = 0b11,
 }
 
@@ -31,6 +38,8 @@ pub enum Hysteresis {
 /// This bit cannot be set to ‘1’ when either of the Lock bits is set (bit 6 and bit 7).
 /// However, it can be cleared to ‘0’ for continuous conversion while locked.
 #[derive(Debug, PartialEq, Clone, Copy, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum ShutdownMode {
     /// Continuous conversion (power-up default)
     ContinuousConversion = 0,
@@ -42,6 +51,8 @@ pub enum ShutdownMode {
 /// When enabled, this bit remains set to ‘1’ or locked until cleared by an internal Reset.
 /// This bit can be programmed in Shutdown mode.
 #[derive(Debug, PartialEq, Clone, Copy, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum CriticalTemperatureLock {
     /// Unlocked. T_CRIT register can be written (power-up default)
     Unlocked,
@@ -53,6 +64,8 @@ pub enum CriticalTemperatureLock {
 /// When enabled, this bit remains set to ‘1’ or locked until cleared by an internal Reset.
 /// This bit can be programmed in Shutdown mode.
 #[derive(Debug, PartialEq, Clone, Copy, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum TemperatureWindowLock {
     /// Unlocked; T_UPPER and T_LOWER registers can be written (power-up default)
     Unlocked = 0,
@@ -64,8 +77,11 @@ pub enum TemperatureWindowLock {
 /// This bit can not be set to ‘1’ in Shutdown mode,
 /// but it can be cleared after the device enters Shutdown mode.
 #[derive(Debug, PartialEq, Clone, Copy, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum InterruptClear {
     /// No effect (power-up default)
+    #[cfg_attr(feature = "serde", serde(rename = "NoEffect"))]
     NotEffect = 0,
     /// Clear interrupt output; when read, this bit returns to ‘0’
     Cleared = 1,
@@ -78,6 +94,8 @@ pub enum InterruptClear {
 /// the interrupt, using bit 5 while the device is in Shutdown mode,
 /// then this bit will also be cleared ‘0’.
 #[derive(Debug, PartialEq, Clone, Copy, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum AlertOutputStatus {
     /// Alert output is not asserted by the device (power-up default)
     NotAsserted = 0,
@@ -89,6 +107,8 @@ pub enum AlertOutputStatus {
 /// This bit can not be altered when either of the Lock bits are set (bit 6 and bit 7).
 /// This bit can be programmed in Shutdown mode, but the Alert output will not assert or deassert.
 #[derive(Debug, PartialEq, Clone, Copy, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum AlertOutputControl {
     /// Disabled (power-up default)
     Disabled = 0,
@@ -100,6 +120,8 @@ pub enum AlertOutputControl {
 /// When the Alarm Window Lock bit is set, this bit cannot be altered until unlocked (bit 6).
 /// This bit can be programmed in Shutdown mode, but the Alert output will not assert or deassert.
 #[derive(Debug, PartialEq, Clone, Copy, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum AlertOutputSelect {
     /// Alert output for T_UPPER, T_LOWER and T_CRIT (power-up default)
     UpperLowerCritical = 0,
@@ -111,6 +133,8 @@ pub enum AlertOutputSelect {
 /// This bit cannot be altered when either of the Lock bits are set (bit 6 and bit 7).
 /// This bit can be programmed in Shutdown mode, but the Alert output will not assert or deassert.
 #[derive(Debug, PartialEq, Clone, Copy, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum AlertOutputPolarity {
     /// Active-low (power-up default; pull-up resistor required)
     ActiveLow = 0,
@@ -122,6 +146,8 @@ pub enum AlertOutputPolarity {
 /// This bit cannot be altered when either of the Lock bits are set (bit 6 and bit 7).
 /// This bit can be programmed in Shutdown mode, but the Alert output will not assert or deassert.
 #[derive(Debug, PartialEq, Clone, Copy, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum AlertOutputMode {
     /// Comparator output (power-up default)
     Comparator = 0,
@@ -131,6 +157,7 @@ pub enum AlertOutputMode {
 
 /// Device configuration
 #[derive(Debug, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Configuration {
     /// T_HYST: T_UPPER and T_LOWER Limit Hysteresis bits
     pub hysteresis: Hysteresis,
@@ -181,19 +208,90 @@ impl Default for Configuration {
     }
 }
 
+impl fmt::Display for Configuration {
+    /// Formats as `hysteresis=1.5 shutdown=off critical-lock=off window-lock=off interrupt-clear=off
+    /// alert-output=on alert-select=all alert-polarity=low alert-mode=comparator alert-status=off`
+    /// on one line, hysteresis in °C
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let switch = |on: bool| if on { "on" } else { "off" };
+        write!(
+            f,
+            "hysteresis={} shutdown={} critical-lock={} window-lock={} interrupt-clear={} \
+             alert-output={} alert-select={} alert-polarity={} alert-mode={} alert-status={}",
+            match self.hysteresis {
+                Hysteresis::Deg0C => "0",
+                Hysteresis::Deg1_5C => "1.5",
+                Hysteresis::Deg3_0C => "3",
+                Hysteresis::Deg6_0C => "6",
+            },
+            switch(self.shutdown_mode == ShutdownMode::Shutdown),
+            switch(self.critical_temperature_lock == CriticalTemperatureLock::Locked),
+            switch(self.temperature_window_lock == TemperatureWindowLock::Locked),
+            switch(self.interrupt_clear == InterruptClear::Cleared),
+            switch(self.alert_output_control == AlertOutputControl::Enabled),
+            match self.alert_output_select {
+                AlertOutputSelect::UpperLowerCritical => "all",
+                AlertOutputSelect::CriticalOnly => "critical",
+            },
+            match self.alert_output_polarity {
+                AlertOutputPolarity::ActiveLow => "low",
+                AlertOutputPolarity::ActiveHigh => "high",
+            },
+            match self.alert_output_mode {
+                AlertOutputMode::Comparator => "comparator",
+                AlertOutputMode::Interrupt => "interrupt",
+            },
+            switch(self.alert_output_status == AlertOutputStatus::Asserted),
+        )
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Configuration {
+    /// Formats with the `Display` field names, encoded as the hysteresis string and the register bits
+    fn format(&self, f: defmt::Formatter) {
+        let hysteresis = match self.hysteresis {
+            Hysteresis::Deg0C => defmt::intern!("0"),
+            Hysteresis::Deg1_5C => defmt::intern!("1.5"),
+            Hysteresis::Deg3_0C => defmt::intern!("3"),
+            Hysteresis::Deg6_0C => defmt::intern!("6"),
+        };
+        let raw: Raw = (*self).into();
+        defmt::write!(
+            f,
+            "hysteresis={0=istr} shutdown={1=8..9} critical-lock={1=7..8} window-lock={1=6..7} \
+             interrupt-clear={1=5..6} alert-output={1=3..4} alert-select={1=2..3} \
+             alert-polarity={1=1..2} alert-mode={1=0..1} alert-status={1=4..5}",
+            hysteresis,
+            u16::from_be_bytes([raw[0], raw[1]])
+        )
+    }
+}
+
+impl Configuration {
+    /// Configuration without the self-clearing Interrupt Clear bit and read-only Alert Output Status bit
+    pub(crate) fn without_status(self) -> Self {
+        Configuration {
+            interrupt_clear: InterruptClear::NotEffect,
+            alert_output_status: AlertOutputStatus::NotAsserted,
+            ..self
+        }
+    }
+}
+
 impl From<Raw> for Configuration {
     fn from(raw: Raw) -> Self {
         let (msb, lsb) = (raw[0], raw[1]);
         Configuration {
-            hysteresis: Hysteresis::from_u8(msb & 0b11 << 1).unwrap(),
+            hysteresis: Hysteresis::from_u8(msb >> 1 & 0b11).unwrap(),
             shutdown_mode: ShutdownMode::from_u8(msb & 1).unwrap(),
-            critical_temperature_lock: CriticalTemperatureLock::from_u8(lsb & 1 << 7).unwrap(),
-            temperature_window_lock: TemperatureWindowLock::from_u8(lsb & 1 << 6).unwrap(),
-            interrupt_clear: InterruptClear::from_u8(lsb & 1 << 5).unwrap(),
-            alert_output_status: AlertOutputStatus::from_u8(lsb & 1 << 3).unwrap(),
-            alert_output_control: AlertOutputControl::from_u8(lsb & 1 << 3).unwrap(),
-            alert_output_select: AlertOutputSelect::from_u8(lsb & 1 << 2).unwrap(),
-            alert_output_polarity: AlertOutputPolarity::from_u8(lsb & 1 << 1).unwrap(),
+            critical_temperature_lock: CriticalTemperatureLock::from_u8(lsb >> 7 & 1).unwrap(),
+            temperature_window_lock: TemperatureWindowLock::from_u8(lsb >> 6 & 1).unwrap(),
+            interrupt_clear: InterruptClear::from_u8(lsb >> 5 & 1).unwrap(),
+            alert_output_status: AlertOutputStatus::from_u8(lsb >> 4 & 1).unwrap(),
+            alert_output_control: AlertOutputControl::from_u8(lsb >> 3 & 1).unwrap(),
+            alert_output_select: AlertOutputSelect::from_u8(lsb >> 2 & 1).unwrap(),
+            alert_output_polarity: AlertOutputPolarity::from_u8(lsb >> 1 & 1).unwrap(),
             alert_output_mode: AlertOutputMode::from_u8(lsb & 1).unwrap(),
         }
     }
@@ -201,16 +299,15 @@ impl From<Raw> for Configuration {
 
 impl Into<Raw> for Configuration {
     fn into(self) -> Raw {
-        let (mut msb, mut lsb) = (0, 0);
-        msb += self.hysteresis as u8 + self.shutdown_mode as u8;
-        lsb += self.critical_temperature_lock as u8
-            + self.temperature_window_lock as u8
-            + self.interrupt_clear as u8
-            + self.alert_output_status as u8
-            + self.alert_output_control as u8
-            + self.alert_output_select as u8
-            + self.alert_output_polarity as u8
-            + self.alert_output_mode as u8;
+        let msb = (self.hysteresis as u8) << 1 | self.shutdown_mode as u8;
+        let lsb = (self.critical_temperature_lock as u8) << 7
+            | (self.temperature_window_lock as u8) << 6
+            | (self.interrupt_clear as u8) << 5
+            | (self.alert_output_status as u8) << 4
+            | (self.alert_output_control as u8) << 3
+            | (self.alert_output_select as u8) << 2
+            | (self.alert_output_polarity as u8) << 1
+            | self.alert_output_mode as u8;
         [msb, lsb].into()
     }
 }
@@ -221,17 +318,18 @@ impl<I2C> MCP9808<I2C> {
     where
         I2C: i2c::WriteRead<Error = Err>,
     {
-        self.i2c_interface
-            .read_register(Register::ConfigurationRegister)
+        self.read_register(Register::ConfigurationRegister)
             .map(Configuration::from)
     }
 
     /// Write `Configuration` to `ConfigurationRegister`
-    pub fn write_configuration<Err>(&mut self, configuration: Configuration) -> Result<(), Err>
+    pub fn write_configuration<Err>(
+        &mut self,
+        configuration: Configuration,
+    ) -> Result<(), verify::Error<Err>>
     where
-        I2C: i2c::Write<Error = Err>,
+        I2C: i2c::Write<Error = Err> + i2c::WriteRead<Error = Err>,
     {
-        self.i2c_interface
-            .write_register(Register::ConfigurationRegister, configuration.into())
+        self.write_register(Register::ConfigurationRegister, configuration.into())
     }
 }
@@ -0,0 +1,493 @@
+//! Temperature Data Logging
+//!
+//! Timestamped measurements are logged as CSV or JSON lines:
+//!
+//! ```text
+//! timestamp_ms,address,temperature_c,critical,upper,lower,resolution_c
+//! 1700000000000,0x18,25.25,false,false,false,0.0625
+//!
+//! {"timestamp_ms":1700000000000,"address":"0x18","temperature_c":25.25,"critical":false,"upper":false,"lower":false,"resolution_c":0.0625}
+//! ```
+//!
+//! Temperatures are written in Celsius (`temperature_c`) or Millicelsius (`temperature_mc`).
+//! `LogReader` reads both formats back, to replay captures into `statistics` and `filter`.
+
+use crate::{
+    resolution::Resolution,
+    temperature::{Millicelsius, TemperatureMeasurement},
+};
+use std::{
+    fmt::Write as _,
+    fs::File,
+    io::{self, BufRead, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+/// Data logging error
+#[derive(Debug)]
+pub enum Error {
+    /// Log could not be read or written
+    Io(io::Error),
+    /// Log line could not be parsed
+    Parse {
+        /// Line number, counting from 1
+        line: usize,
+    },
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+/// Log file format
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Format {
+    /// Comma-separated values with a header line
+    Csv,
+    /// One JSON object per line
+    JsonLines,
+}
+
+/// Unit of logged temperatures
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Unit {
+    /// Decimal Celsius, `temperature_c`
+    Celsius,
+    /// Integer Millicelsius, `temperature_mc`
+    Millicelsius,
+}
+
+impl Unit {
+    fn suffix(self) -> &'static str {
+        match self {
+            Unit::Celsius => "c",
+            Unit::Millicelsius => "mc",
+        }
+    }
+
+    fn format(self, temperature: Millicelsius) -> String {
+        match self {
+            Unit::Celsius => format_celsius(temperature),
+            Unit::Millicelsius => temperature.0.to_string(),
+        }
+    }
+
+    fn parse(self, value: &str) -> Option<Millicelsius> {
+        match self {
+            Unit::Celsius => format!("{} °C", value).parse().ok(),
+            Unit::Millicelsius => value.parse().ok().map(Millicelsius),
+        }
+    }
+}
+
+/// Logged measurement
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Record {
+    /// Milliseconds since the Unix epoch
+    pub timestamp_ms: u64,
+
+    /// Sensor address
+    pub address: u8,
+
+    /// Temperature and alert flags
+    pub measurement: TemperatureMeasurement<Millicelsius>,
+
+    /// Resolution the temperature was measured with
+    pub resolution: Resolution,
+}
+
+impl Record {
+    /// Record as one line, without line terminator
+    pub fn to_line(&self, format: Format, unit: Unit) -> String {
+        let measurement = &self.measurement;
+        let mut line = String::new();
+        let _ = match format {
+            Format::Csv => write!(
+                line,
+                "{},{:#04x},{},{},{},{},{}",
+                self.timestamp_ms,
+                self.address,
+                unit.format(measurement.temperature),
+                measurement.is_critical,
+                measurement.is_upper,
+                measurement.is_lower,
+                self.resolution
+            ),
+            Format::JsonLines => write!(
+                line,
+                "{{\"timestamp_ms\":{},\"address\":\"{:#04x}\",\"temperature_{}\":{},\"critical\":{},\"upper\":{},\"lower\":{},\"resolution_c\":{}}}",
+                self.timestamp_ms,
+                self.address,
+                unit.suffix(),
+                unit.format(measurement.temperature),
+                measurement.is_critical,
+                measurement.is_upper,
+                measurement.is_lower,
+                self.resolution
+            ),
+        };
+        line
+    }
+
+    fn from_fields<'a, F>(mut field: F, unit: Unit) -> Option<Self>
+    where
+        F: FnMut(&str) -> Option<&'a str>,
+    {
+        let temperature = format!("temperature_{}", unit.suffix());
+        Some(Record {
+            timestamp_ms: field("timestamp_ms")?.parse().ok()?,
+            address: u8::from_str_radix(field("address")?.strip_prefix("0x")?, 16).ok()?,
+            measurement: TemperatureMeasurement {
+                temperature: unit.parse(field(&temperature)?)?,
+                is_critical: field("critical")?.parse().ok()?,
+                is_upper: field("upper")?.parse().ok()?,
+                is_lower: field("lower")?.parse().ok()?,
+            },
+            resolution: field("resolution_c")?.parse().ok()?,
+        })
+    }
+}
+
+/// Writer of `Record`s in one `Format`
+///
+/// # Example
+///
+/// ```
+/// use mcp9808::{
+///     datalog::{Format, LogWriter, Record, Unit},
+///     resolution::Resolution,
+///     temperature::{Millicelsius, TemperatureMeasurement},
+/// };
+///
+/// let mut writer = LogWriter::new(Vec::new(), Format::Csv, Unit::Celsius);
+/// writer
+///     .write(&Record {
+///         timestamp_ms: 1_000,
+///         address: 0x18,
+///         measurement: TemperatureMeasurement {
+///             temperature: Millicelsius(-3_250),
+///             is_critical: false,
+///             is_upper: false,
+///             is_lower: true,
+///         },
+///         resolution: Resolution::Deg0_25C,
+///     })
+///     .unwrap();
+///
+/// assert_eq!(
+///     "timestamp_ms,address,temperature_c,critical,upper,lower,resolution_c\n\
+///      1000,0x18,-3.25,false,false,true,0.25\n",
+///     String::from_utf8(writer.into_inner()).unwrap()
+/// );
+/// ```
+pub struct LogWriter<W> {
+    writer: W,
+    format: Format,
+    unit: Unit,
+    header_written: bool,
+}
+
+impl<W> LogWriter<W>
+where
+    W: Write,
+{
+    /// Creates new log writer, CSV logs start with a header line
+    pub fn new(writer: W, format: Format, unit: Unit) -> Self {
+        LogWriter {
+            writer,
+            format,
+            unit,
+            header_written: false,
+        }
+    }
+
+    /// Write `record` and return the number of bytes written
+    pub fn write(&mut self, record: &Record) -> io::Result<u64> {
+        let mut text = String::new();
+        if self.format == Format::Csv && !self.header_written {
+            text.push_str(&csv_header(self.unit));
+            text.push('\n');
+        }
+        text.push_str(&record.to_line(self.format, self.unit));
+        text.push('\n');
+        self.writer.write_all(text.as_bytes())?;
+        self.header_written = true;
+        Ok(text.len() as u64)
+    }
+
+    /// Flush the inner writer
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Release the inner writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Condition starting a new log file, rotation is disabled with both set to `None`
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Rotation {
+    /// Largest file size in bytes
+    pub max_bytes: Option<u64>,
+
+    /// Longest time span of records in one file, in milliseconds
+    pub max_duration_ms: Option<u64>,
+}
+
+/// Log written to numbered files, `capture.csv` is written as `capture-0001.csv`,
+/// `capture-0002.csv`, ... skipping files which already exist
+pub struct RotatingLog {
+    path: PathBuf,
+    format: Format,
+    unit: Unit,
+    rotation: Rotation,
+    writer: Option<LogWriter<BufWriter<File>>>,
+    current: Option<PathBuf>,
+    index: u32,
+    bytes: u64,
+    started_ms: u64,
+}
+
+impl RotatingLog {
+    /// Creates new log, the first file is created with the first record
+    pub fn new<P>(path: P, format: Format, unit: Unit, rotation: Rotation) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        RotatingLog {
+            path: path.into(),
+            format,
+            unit,
+            rotation,
+            writer: None,
+            current: None,
+            index: 0,
+            bytes: 0,
+            started_ms: 0,
+        }
+    }
+
+    /// File currently written
+    pub fn current_path(&self) -> Option<&Path> {
+        self.current.as_deref()
+    }
+
+    /// Write `record`, starting a new file first when the `Rotation` condition is met
+    pub fn write(&mut self, record: &Record) -> Result<(), Error> {
+        let full = self.rotation.max_bytes.map(|max| self.bytes >= max) == Some(true);
+        let expired = self
+            .rotation
+            .max_duration_ms
+            .map(|max| record.timestamp_ms.saturating_sub(self.started_ms) >= max)
+            == Some(true);
+        if self.writer.is_none() || full || expired {
+            self.rotate(record.timestamp_ms)?;
+        }
+        if let Some(writer) = &mut self.writer {
+            self.bytes += writer.write(record)?;
+        }
+        Ok(())
+    }
+
+    /// Flush the current file
+    pub fn flush(&mut self) -> Result<(), Error> {
+        if let Some(writer) = &mut self.writer {
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    fn rotate(&mut self, timestamp_ms: u64) -> Result<(), Error> {
+        self.flush()?;
+        let path = loop {
+            self.index += 1;
+            let path = self.numbered_path(self.index);
+            if !path.exists() {
+                break path;
+            }
+        };
+        let file = File::create(&path)?;
+        self.writer = Some(LogWriter::new(BufWriter::new(file), self.format, self.unit));
+        self.current = Some(path);
+        self.bytes = 0;
+        self.started_ms = timestamp_ms;
+        Ok(())
+    }
+
+    fn numbered_path(&self, index: u32) -> PathBuf {
+        let stem = self
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let name = match self.path.extension() {
+            Some(extension) => format!("{}-{:04}.{}", stem, index, extension.to_string_lossy()),
+            None => format!("{}-{:04}", stem, index),
+        };
+        self.path.with_file_name(name)
+    }
+}
+
+impl Drop for RotatingLog {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// Reader of `Record`s from CSV or JSON lines logs, detecting the format per line
+///
+/// # Example
+///
+/// ```
+/// use i2c_interface::generic_array::typenum::consts::U3;
+/// use mcp9808::{
+///     datalog::LogReader,
+///     filter::{Filter, Median},
+///     statistics::TemperatureStats,
+///     temperature::Millicelsius,
+/// };
+///
+/// let log = "\
+/// timestamp_ms,address,temperature_mc,critical,upper,lower,resolution_c
+/// 0,0x18,20000,false,false,false,0.0625
+/// 1000,0x18,85000,false,true,false,0.0625
+/// 2000,0x18,21000,false,false,false,0.0625
+/// ";
+///
+/// let mut stats = TemperatureStats::new();
+/// let mut median = Median::<U3>::new();
+/// for record in LogReader::new(log.as_bytes()) {
+///     let record = record.unwrap();
+///     let filtered = median.filter(record.measurement.temperature);
+///     stats.add(filtered, record.timestamp_ms);
+/// }
+///
+/// assert_eq!(3, stats.count());
+/// assert_eq!(Some(Millicelsius(20_000)), stats.min().map(|min| min.temperature));
+/// ```
+pub struct LogReader<R> {
+    lines: io::Lines<R>,
+    line: usize,
+    csv_unit: Option<Unit>,
+}
+
+impl<R> LogReader<R>
+where
+    R: BufRead,
+{
+    /// Creates new log reader
+    pub fn new(reader: R) -> Self {
+        LogReader {
+            lines: reader.lines(),
+            line: 0,
+            csv_unit: None,
+        }
+    }
+
+    fn parse(&mut self, line: &str) -> Option<Record> {
+        if line.starts_with('{') {
+            return parse_json(line);
+        }
+        let fields: Vec<&str> = line.split(',').collect();
+        let unit = self.csv_unit?;
+        let header = csv_header(unit);
+        let names: Vec<&str> = header.split(',').collect();
+        if fields.len() != names.len() {
+            return None;
+        }
+        Record::from_fields(
+            |name| {
+                let index = names.iter().position(|&field| field == name)?;
+                Some(fields[index])
+            },
+            unit,
+        )
+    }
+
+    /// CSV header line selecting the temperature unit of following rows
+    fn parse_header(line: &str) -> Option<Unit> {
+        [Unit::Celsius, Unit::Millicelsius]
+            .iter()
+            .find(|&&unit| line == csv_header(unit))
+            .cloned()
+    }
+}
+
+impl<R> Iterator for LogReader<R>
+where
+    R: BufRead,
+{
+    type Item = Result<Record, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(error) => return Some(Err(error.into())),
+            };
+            self.line += 1;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(unit) = Self::parse_header(line) {
+                self.csv_unit = Some(unit);
+                continue;
+            }
+            return Some(self.parse(line).ok_or(Error::Parse { line: self.line }));
+        }
+    }
+}
+
+/// CSV header line for temperatures in `unit`
+fn csv_header(unit: Unit) -> String {
+    format!(
+        "timestamp_ms,address,temperature_{},critical,upper,lower,resolution_c",
+        unit.suffix()
+    )
+}
+
+/// Flat JSON object as written by `Record::to_line`
+fn parse_json(line: &str) -> Option<Record> {
+    let body = line.strip_prefix('{')?.strip_suffix('}')?;
+    let mut pairs = Vec::new();
+    for pair in body.split(',') {
+        let mut parts = pair.splitn(2, ':');
+        let key = parts.next()?.trim().strip_prefix('"')?.strip_suffix('"')?;
+        let value = parts.next()?.trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .unwrap_or(value);
+        pairs.push((key, value));
+    }
+    let field = |name: &str| {
+        pairs
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| *value)
+    };
+    let unit = if field("temperature_c").is_some() {
+        Unit::Celsius
+    } else {
+        Unit::Millicelsius
+    };
+    Record::from_fields(field, unit)
+}
+
+/// Exact decimal Celsius, e.g. `-3.25`
+fn format_celsius(temperature: Millicelsius) -> String {
+    let sign = if temperature.0 < 0 { "-" } else { "" };
+    let millicelsius = temperature.0.unsigned_abs();
+    let fraction = format!("{:03}", millicelsius % 1_000);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        format!("{}{}", sign, millicelsius / 1_000)
+    } else {
+        format!("{}{}.{}", sign, millicelsius / 1_000, fraction)
+    }
+}
@@ -9,12 +9,14 @@ type Raw = GenericArray<u8, U2>;
 
 /// Bit 15 to bit 8 are used for `DeviceId`.
 #[derive(Debug, PartialEq, PartialOrd, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DeviceId(pub u8);
 
 /// Bit 7 to bit 0 are used for `DeviceRevision`.
 /// The revision begins with 0x00 (hex) for the first release,
 /// with the number being incremented as revised versions are released.
 #[derive(Debug, PartialEq, PartialOrd, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DeviceRevision(pub u8);
 
 impl DeviceId {
@@ -24,14 +26,27 @@ impl DeviceId {
     }
 }
 
+#[cfg(feature = "defmt")]
+impl defmt::Format for DeviceId {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "{=u8:#04x}", self.0)
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for DeviceRevision {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "{=u8:#04x}", self.0)
+    }
+}
+
 impl<I2C> MCP9808<I2C> {
     /// Read `DeviceId` and `DeviceRevision` from `DeviceIdRevisionRegister`
     pub fn read_device_information<Err>(&mut self) -> Result<(DeviceId, DeviceRevision), Err>
     where
         I2C: i2c::WriteRead<Error = Err>,
     {
-        self.i2c_interface
-            .read_register(Register::DeviceIdRevisionRegister)
+        self.read_register(Register::DeviceIdRevisionRegister)
             .map(|raw: Raw| (DeviceId(raw[0]), DeviceRevision(raw[1])))
     }
 }
@@ -0,0 +1,190 @@
+//! Fan Curve
+
+use crate::{
+    hal::{blocking::i2c, PwmPin},
+    temperature::{Millicelsius, TemperatureMeasurement},
+    MCP9808,
+};
+use i2c_interface::generic_array::{ArrayLength, GenericArray};
+use num_traits::{cast, ToPrimitive};
+
+/// Full duty cycle in per mille
+pub const MAX_DUTY: u16 = 1_000;
+
+/// Fan curve point mapping temperature to duty cycle in per mille
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct FanCurvePoint {
+    /// Temperature at which `duty` applies
+    pub temperature: Millicelsius,
+
+    /// Duty cycle in per mille, `0..=MAX_DUTY`
+    pub duty: u16,
+}
+
+impl FanCurvePoint {
+    /// Creates new point from temperature `Unit` and duty cycle in per mille
+    pub fn new<Unit>(temperature: Unit, duty: u16) -> Self
+    where
+        Unit: Into<Millicelsius>,
+    {
+        FanCurvePoint {
+            temperature: temperature.into(),
+            duty,
+        }
+    }
+}
+
+/// Fan curve error
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Error {
+    /// Curve has no points
+    Empty,
+    /// Point temperatures are not strictly increasing
+    Unsorted,
+    /// Point duty cycle is greater than `MAX_DUTY`
+    DutyOutOfRange,
+}
+
+/// Piecewise-linear fan curve with `N` points
+///
+/// Duty cycle is interpolated between points using integer arithmetic
+/// and held at the first and last point outside of the curve.
+/// Rising temperature is followed immediately, while falling temperature
+/// lowers the duty cycle only after it drops by more than the hysteresis.
+///
+/// # Example
+///
+/// ```
+/// use mcp9808::{
+///     fan_curve::{FanCurve, FanCurvePoint},
+///     temperature::Celsius,
+/// };
+/// use i2c_interface::generic_array::arr;
+///
+/// let points = arr![FanCurvePoint;
+///     FanCurvePoint::new(Celsius(30.0), 200),
+///     FanCurvePoint::new(Celsius(60.0), 1_000)
+/// ];
+/// let mut curve = FanCurve::new(points, Celsius(2.0)).unwrap();
+/// assert_eq!(200, curve.duty(Celsius(25.0)));
+/// assert_eq!(600, curve.duty(Celsius(45.0)));
+/// assert_eq!(600, curve.duty(Celsius(43.5)));
+/// assert_eq!(1_000, curve.duty(Celsius(75.0)));
+/// ```
+pub struct FanCurve<N>
+where
+    N: ArrayLength<FanCurvePoint>,
+{
+    points: GenericArray<FanCurvePoint, N>,
+    hysteresis: Millicelsius,
+    effective_temperature: Option<Millicelsius>,
+}
+
+impl<N> FanCurve<N>
+where
+    N: ArrayLength<FanCurvePoint>,
+{
+    /// Creates new fan curve from points sorted by temperature and `hysteresis`
+    pub fn new<Unit>(
+        points: GenericArray<FanCurvePoint, N>,
+        hysteresis: Unit,
+    ) -> Result<Self, Error>
+    where
+        Unit: Into<Millicelsius>,
+    {
+        if points.is_empty() {
+            return Err(Error::Empty);
+        }
+        if points
+            .windows(2)
+            .any(|pair| pair[0].temperature >= pair[1].temperature)
+        {
+            return Err(Error::Unsorted);
+        }
+        if points.iter().any(|point| point.duty > MAX_DUTY) {
+            return Err(Error::DutyOutOfRange);
+        }
+        Ok(FanCurve {
+            points,
+            hysteresis: hysteresis.into(),
+            effective_temperature: None,
+        })
+    }
+
+    /// Curve points
+    pub fn points(&self) -> &[FanCurvePoint] {
+        &self.points
+    }
+
+    /// Duty cycle in per mille for `temperature`, without hysteresis
+    pub fn interpolate<Unit>(&self, temperature: Unit) -> u16
+    where
+        Unit: Into<Millicelsius>,
+    {
+        let temperature = temperature.into();
+        let first = self.points[0];
+        let last = self.points[self.points.len() - 1];
+        if temperature <= first.temperature {
+            return first.duty;
+        }
+        if temperature >= last.temperature {
+            return last.duty;
+        }
+
+        let index = self
+            .points
+            .iter()
+            .position(|point| point.temperature > temperature)
+            .unwrap_or(self.points.len() - 1);
+        let (lower, upper) = (self.points[index - 1], self.points[index]);
+
+        let span = i64::from(upper.temperature.0) - i64::from(lower.temperature.0);
+        let offset = i64::from(temperature.0) - i64::from(lower.temperature.0);
+        let delta = i64::from(upper.duty) - i64::from(lower.duty);
+        (i64::from(lower.duty) + delta * offset / span) as u16
+    }
+
+    /// Duty cycle in per mille for `temperature`, with hysteresis applied
+    pub fn duty<Unit>(&mut self, temperature: Unit) -> u16
+    where
+        Unit: Into<Millicelsius>,
+    {
+        let temperature = temperature.into();
+        let release = Millicelsius(temperature.0.saturating_add(self.hysteresis.0));
+        let effective = match self.effective_temperature {
+            Some(effective) if effective < temperature => temperature,
+            Some(effective) if effective > release => release,
+            Some(effective) => effective,
+            None => temperature,
+        };
+        self.effective_temperature = Some(effective);
+        self.interpolate(effective)
+    }
+
+    /// Forget temperature history used for hysteresis
+    pub fn reset(&mut self) {
+        self.effective_temperature = None;
+    }
+
+    /// Read ambient temperature from `MCP9808` and set duty cycle of `PwmPin`
+    pub fn drive<I2C, Err, Pin>(
+        &mut self,
+        mcp9808: &mut MCP9808<I2C>,
+        pin: &mut Pin,
+    ) -> Result<u16, Err>
+    where
+        I2C: i2c::WriteRead<Error = Err>,
+        Pin: PwmPin,
+        Pin::Duty: num_traits::NumCast + ToPrimitive,
+    {
+        let measurement: TemperatureMeasurement<Millicelsius> =
+            mcp9808.read_ambient_temperature()?;
+        let duty = self.duty(measurement.temperature);
+
+        let max_duty = pin.get_max_duty().to_u64().unwrap_or(0);
+        if let Some(pin_duty) = cast(max_duty * u64::from(duty) / u64::from(MAX_DUTY)) {
+            pin.set_duty(pin_duty);
+        }
+        Ok(duty)
+    }
+}
@@ -0,0 +1,219 @@
+//! Fault Injection for Robustness Tests
+
+use crate::{hal::blocking::i2c, registers::Register};
+
+/// Longest write forwarded to the inner bus, register pointer included
+const MAX_WRITE_LEN: usize = 8;
+
+/// Bus error of `FaultyI2c`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Error<BusError> {
+    /// Inner bus error
+    Bus(BusError),
+    /// Injected NACK
+    InjectedNack,
+}
+
+/// Injected fault
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Fault {
+    /// Transaction is not acknowledged and fails
+    Nack,
+    /// Only the given number of data bytes is transferred,
+    /// missing read bytes are read as 0xFF from the released bus
+    Truncate(usize),
+    /// Data bytes are XORed with the mask
+    Corrupt(u8),
+}
+
+/// Transactions the fault is injected into
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Trigger {
+    /// No transaction
+    Never,
+    /// Every `n`-th transaction, counting from 1
+    EveryNth(u32),
+    /// Every transaction addressing the register
+    Register(Register),
+    /// Transactions chosen pseudo-randomly from `seed`, with probability `per_mille` / 1000
+    Random {
+        /// Seed of the pseudo-random sequence, reproducing the same faults
+        seed: u32,
+        /// Probability of a fault in each transaction
+        per_mille: u16,
+    },
+}
+
+/// I2C bus injecting a `Fault` into transactions selected by a `Trigger`
+///
+/// # Example
+///
+/// ```
+/// use mcp9808::{
+///     fault::{Error, Fault, FaultyI2c, Trigger},
+///     simulator::Simulator,
+///     Register, SlaveAddress, MCP9808,
+/// };
+///
+/// let bus = FaultyI2c::new(
+///     Simulator::new(SlaveAddress::Default),
+///     Fault::Nack,
+///     Trigger::Register(Register::ResolutionRegister),
+/// );
+/// let mut mcp9808 = MCP9808::new(bus, SlaveAddress::Default);
+///
+/// assert_eq!(Err(Error::InjectedNack), mcp9808.read_resolution());
+/// assert!(mcp9808.read_configuration().is_ok());
+/// assert_eq!(1, mcp9808.i2c().injected_count());
+/// ```
+pub struct FaultyI2c<I2C> {
+    i2c: I2C,
+    fault: Fault,
+    trigger: Trigger,
+    random_state: u32,
+    transaction_count: u32,
+    injected_count: u32,
+}
+
+impl<I2C> FaultyI2c<I2C> {
+    /// Creates new bus injecting `fault` into transactions of `i2c` selected by `trigger`
+    pub fn new(i2c: I2C, fault: Fault, trigger: Trigger) -> Self {
+        let mut bus = FaultyI2c {
+            i2c,
+            fault,
+            trigger: Trigger::Never,
+            random_state: 0,
+            transaction_count: 0,
+            injected_count: 0,
+        };
+        bus.set_trigger(trigger);
+        bus
+    }
+
+    /// Change injected `Fault`
+    pub fn set_fault(&mut self, fault: Fault) {
+        self.fault = fault;
+    }
+
+    /// Change `Trigger`, restarting transaction counting and the random sequence
+    pub fn set_trigger(&mut self, trigger: Trigger) {
+        self.trigger = trigger;
+        self.transaction_count = 0;
+        if let Trigger::Random { seed, .. } = trigger {
+            // xorshift state must not be zero
+            self.random_state = if seed == 0 { 0x9E37_79B9 } else { seed };
+        }
+    }
+
+    /// Number of transactions since the `Trigger` was set
+    pub fn transaction_count(&self) -> u32 {
+        self.transaction_count
+    }
+
+    /// Number of transactions a fault was injected into
+    pub fn injected_count(&self) -> u32 {
+        self.injected_count
+    }
+
+    /// Shared reference to the inner `I2C`
+    pub fn inner(&self) -> &I2C {
+        &self.i2c
+    }
+
+    /// Mutable reference to the inner `I2C`
+    pub fn inner_mut(&mut self) -> &mut I2C {
+        &mut self.i2c
+    }
+
+    /// Release the inner `I2C`
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    /// Fault to inject into the next transaction writing `bytes`
+    fn next_fault(&mut self, bytes: &[u8]) -> Option<Fault> {
+        self.transaction_count = self.transaction_count.wrapping_add(1);
+        let triggered = match self.trigger {
+            Trigger::Never => false,
+            Trigger::EveryNth(n) => {
+                n > 0 && self.transaction_count / n * n == self.transaction_count
+            }
+            Trigger::Register(register) => bytes
+                .first()
+                .map(|&pointer| Register::from_pointer(pointer) == Some(register))
+                .unwrap_or(false),
+            Trigger::Random { per_mille, .. } => self.next_random() % 1_000 < u32::from(per_mille),
+        };
+        if triggered {
+            self.injected_count = self.injected_count.saturating_add(1);
+            Some(self.fault)
+        } else {
+            None
+        }
+    }
+
+    /// xorshift32
+    fn next_random(&mut self) -> u32 {
+        let mut state = self.random_state;
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        self.random_state = state;
+        state
+    }
+}
+
+impl<I2C, Err> i2c::Write for FaultyI2c<I2C>
+where
+    I2C: i2c::Write<Error = Err>,
+{
+    type Error = Error<Err>;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        let len = bytes.len().min(MAX_WRITE_LEN);
+        let mut buffer = [0; MAX_WRITE_LEN];
+        buffer[..len].copy_from_slice(&bytes[..len]);
+        let mut write = &mut buffer[..len];
+
+        match self.next_fault(bytes) {
+            Some(Fault::Nack) => return Err(Error::InjectedNack),
+            Some(Fault::Truncate(data_len)) => {
+                write = &mut buffer[..len.min(data_len + 1)];
+            }
+            Some(Fault::Corrupt(mask)) => write.iter_mut().skip(1).for_each(|byte| *byte ^= mask),
+            None => {}
+        }
+        self.i2c.write(address, write).map_err(Error::Bus)
+    }
+}
+
+impl<I2C, Err> i2c::WriteRead for FaultyI2c<I2C>
+where
+    I2C: i2c::WriteRead<Error = Err>,
+{
+    type Error = Error<Err>;
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let fault = self.next_fault(bytes);
+        if fault == Some(Fault::Nack) {
+            return Err(Error::InjectedNack);
+        }
+        self.i2c
+            .write_read(address, bytes, buffer)
+            .map_err(Error::Bus)?;
+        match fault {
+            Some(Fault::Truncate(data_len)) => buffer
+                .iter_mut()
+                .skip(data_len)
+                .for_each(|byte| *byte = 0xFF),
+            Some(Fault::Corrupt(mask)) => buffer.iter_mut().for_each(|byte| *byte ^= mask),
+            _ => {}
+        }
+        Ok(())
+    }
+}
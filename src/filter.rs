@@ -0,0 +1,283 @@
+//! Temperature Filters
+
+use crate::{
+    hal::blocking::i2c,
+    temperature::{divide_rounded, Millicelsius, TemperatureMeasurement, TemperatureUnit},
+    MCP9808,
+};
+use i2c_interface::generic_array::{typenum::NonZero, ArrayLength, GenericArray};
+
+/// Filter error
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Error {
+    /// Smoothing factor is not within `1..=1000`
+    AlphaOutOfRange,
+}
+
+/// Filter smoothing successive temperature samples
+pub trait Filter {
+    /// Feed new `sample` and return filtered temperature
+    fn filter(&mut self, sample: Millicelsius) -> Millicelsius;
+
+    /// Forget all previous samples
+    fn reset(&mut self);
+}
+
+/// Fixed-size ring buffer of the last `N` samples
+struct Window<N>
+where
+    N: ArrayLength<i32> + NonZero,
+{
+    samples: GenericArray<i32, N>,
+    next: usize,
+    len: usize,
+}
+
+impl<N> Window<N>
+where
+    N: ArrayLength<i32> + NonZero,
+{
+    fn new() -> Self {
+        Window {
+            samples: GenericArray::default(),
+            next: 0,
+            len: 0,
+        }
+    }
+
+    /// Push `sample`, returning the sample it replaced
+    fn push(&mut self, sample: i32) -> Option<i32> {
+        let replaced = if self.len == self.samples.len() {
+            Some(self.samples[self.next])
+        } else {
+            self.len += 1;
+            None
+        };
+        self.samples[self.next] = sample;
+        self.next = (self.next + 1) % self.samples.len();
+        replaced
+    }
+
+    fn samples(&self) -> &[i32] {
+        &self.samples[..self.len]
+    }
+
+    fn clear(&mut self) {
+        self.next = 0;
+        self.len = 0;
+    }
+}
+
+/// Moving average of the last `N` samples
+pub struct MovingAverage<N>
+where
+    N: ArrayLength<i32> + NonZero,
+{
+    window: Window<N>,
+    sum: i64,
+}
+
+impl<N> MovingAverage<N>
+where
+    N: ArrayLength<i32> + NonZero,
+{
+    /// Creates new moving average of `N` samples
+    pub fn new() -> Self {
+        MovingAverage {
+            window: Window::new(),
+            sum: 0,
+        }
+    }
+}
+
+impl<N> Default for MovingAverage<N>
+where
+    N: ArrayLength<i32> + NonZero,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N> Filter for MovingAverage<N>
+where
+    N: ArrayLength<i32> + NonZero,
+{
+    fn filter(&mut self, sample: Millicelsius) -> Millicelsius {
+        self.sum += i64::from(sample.0);
+        if let Some(replaced) = self.window.push(sample.0) {
+            self.sum -= i64::from(replaced);
+        }
+        Millicelsius(divide_rounded(self.sum, self.window.len as i64) as i32)
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+        self.sum = 0;
+    }
+}
+
+/// Median of the last `N` samples, rejects single sample spikes
+pub struct Median<N>
+where
+    N: ArrayLength<i32> + NonZero,
+{
+    window: Window<N>,
+    sorted: GenericArray<i32, N>,
+}
+
+impl<N> Median<N>
+where
+    N: ArrayLength<i32> + NonZero,
+{
+    /// Creates new median filter of `N` samples
+    pub fn new() -> Self {
+        Median {
+            window: Window::new(),
+            sorted: GenericArray::default(),
+        }
+    }
+}
+
+impl<N> Default for Median<N>
+where
+    N: ArrayLength<i32> + NonZero,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N> Filter for Median<N>
+where
+    N: ArrayLength<i32> + NonZero,
+{
+    fn filter(&mut self, sample: Millicelsius) -> Millicelsius {
+        self.window.push(sample.0);
+        let samples = self.window.samples();
+        let sorted = &mut self.sorted[..samples.len()];
+        sorted.copy_from_slice(samples);
+        sorted.sort_unstable();
+
+        let middle = sorted.len() / 2;
+        if sorted.len() % 2 == 1 {
+            Millicelsius(sorted[middle])
+        } else {
+            let sum = i64::from(sorted[middle - 1]) + i64::from(sorted[middle]);
+            Millicelsius(divide_rounded(sum, 2) as i32)
+        }
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+/// Exponential moving average, first sample is passed through unchanged
+pub struct ExponentialMovingAverage {
+    alpha: u16,
+    average: Option<i32>,
+}
+
+impl ExponentialMovingAverage {
+    /// Creates new exponential moving average with smoothing factor `alpha` in per mille,
+    /// `1..=1000`, where `1000` disables smoothing
+    pub fn new(alpha: u16) -> Result<Self, Error> {
+        if alpha == 0 || alpha > 1_000 {
+            return Err(Error::AlphaOutOfRange);
+        }
+        Ok(ExponentialMovingAverage {
+            alpha,
+            average: None,
+        })
+    }
+}
+
+impl Filter for ExponentialMovingAverage {
+    fn filter(&mut self, sample: Millicelsius) -> Millicelsius {
+        let average = match self.average {
+            Some(average) => {
+                let delta = i64::from(sample.0) - i64::from(average);
+                average + divide_rounded(delta * i64::from(self.alpha), 1_000) as i32
+            }
+            None => sample.0,
+        };
+        self.average = Some(average);
+        Millicelsius(average)
+    }
+
+    fn reset(&mut self) {
+        self.average = None;
+    }
+}
+
+/// `MCP9808` with ambient temperature passed through a `Filter`
+///
+/// # Example
+///
+/// ```
+/// use i2c_interface::generic_array::typenum::consts::U4;
+/// use mcp9808::{
+///     filter::{FilteredSensor, MovingAverage},
+///     simulator::Simulator,
+///     temperature::{Millicelsius, TemperatureMeasurement},
+///     SlaveAddress, MCP9808,
+/// };
+///
+/// let mut simulator = Simulator::new(SlaveAddress::Default);
+/// simulator.set_ambient_temperature(Millicelsius(22_000));
+///
+/// let mcp9808 = MCP9808::new(simulator, SlaveAddress::Default);
+/// let mut sensor = FilteredSensor::new(mcp9808, MovingAverage::<U4>::new());
+/// let measurement: TemperatureMeasurement<Millicelsius> =
+///     sensor.read_ambient_temperature().unwrap();
+/// assert_eq!(Millicelsius(22_000), measurement.temperature);
+/// ```
+pub struct FilteredSensor<I2C, F> {
+    mcp9808: MCP9808<I2C>,
+    filter: F,
+}
+
+impl<I2C, F> FilteredSensor<I2C, F>
+where
+    F: Filter,
+{
+    /// Creates new filtered sensor from `MCP9808` and `Filter`
+    pub fn new(mcp9808: MCP9808<I2C>, filter: F) -> Self {
+        FilteredSensor { mcp9808, filter }
+    }
+
+    /// Read `TemperatureMeasurement` from `AmbientTemperatureRegister` with filtered temperature.
+    /// Alert flags are taken from the latest reading.
+    pub fn read_ambient_temperature<Unit, Err>(
+        &mut self,
+    ) -> Result<TemperatureMeasurement<Unit>, Err>
+    where
+        I2C: i2c::WriteRead<Error = Err>,
+        Unit: From<Millicelsius> + TemperatureUnit,
+    {
+        let measurement: TemperatureMeasurement<Millicelsius> =
+            self.mcp9808.read_ambient_temperature()?;
+        Ok(TemperatureMeasurement {
+            temperature: self.filter.filter(measurement.temperature).into(),
+            is_critical: measurement.is_critical,
+            is_upper: measurement.is_upper,
+            is_lower: measurement.is_lower,
+        })
+    }
+
+    /// Forget all previous samples
+    pub fn reset(&mut self) {
+        self.filter.reset();
+    }
+
+    /// Underlying `MCP9808`, for access to registers other than ambient temperature
+    pub fn sensor(&mut self) -> &mut MCP9808<I2C> {
+        &mut self.mcp9808
+    }
+
+    /// Release the `MCP9808` and `Filter`
+    pub fn release(self) -> (MCP9808<I2C>, F) {
+        (self.mcp9808, self.filter)
+    }
+}
@@ -0,0 +1,229 @@
+//! Sensor Fault and Plausibility Detection
+
+use crate::{
+    hal::blocking::i2c,
+    resolution::Resolution,
+    temperature::{Millicelsius, TemperatureMeasurement, TemperatureUnit},
+    MCP9808,
+};
+
+/// Lowest temperature of the specified operating range
+pub const MIN_TEMPERATURE: Millicelsius = Millicelsius(-40_000);
+
+/// Highest temperature of the specified operating range
+pub const MAX_TEMPERATURE: Millicelsius = Millicelsius(125_000);
+
+/// Default maximum temperature change rate in Millicelsius per millisecond, 40°C/s
+const DEFAULT_MAX_RATE: i32 = 40;
+const DEFAULT_STUCK_THRESHOLD: u32 = 100;
+
+/// Aggregated sensor health status
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct SensorHealth {
+    /// The same temperature was read at least the stuck threshold number of times in a row
+    pub stuck: bool,
+
+    /// Last reading jumped by more than the allowed maximum,
+    /// or has fraction bits finer than the active `Resolution`
+    pub implausible_jump: bool,
+
+    /// Last reading is outside of the -40°C to +125°C operating range
+    pub out_of_range: bool,
+
+    /// Manufacturer ID or Device ID did not match the MCP9808
+    pub identity_mismatch: bool,
+
+    /// Bus errors since the last successful transaction
+    pub consecutive_bus_errors: u32,
+
+    /// Bus errors since the monitor was created or reset
+    pub total_bus_errors: u32,
+}
+
+impl SensorHealth {
+    /// No fault is currently flagged
+    pub fn is_healthy(&self) -> bool {
+        !self.stuck
+            && !self.implausible_jump
+            && !self.out_of_range
+            && !self.identity_mismatch
+            && self.consecutive_bus_errors == 0
+    }
+}
+
+/// Health monitor checking readings and bus transactions of `MCP9808`
+///
+/// # Example
+///
+/// ```
+/// use mcp9808::{
+///     health::HealthMonitor,
+///     resolution::Resolution,
+///     simulator::Simulator,
+///     temperature::{Millicelsius, TemperatureMeasurement},
+///     SlaveAddress, MCP9808,
+/// };
+///
+/// let mut simulator = Simulator::new(SlaveAddress::Default);
+/// simulator.set_ambient_temperature(Millicelsius(23_500));
+/// let mut mcp9808 = MCP9808::new(simulator, SlaveAddress::Default);
+///
+/// let mut monitor = HealthMonitor::new(Resolution::Deg0_0625C);
+/// monitor.check_identity(&mut mcp9808).unwrap();
+/// let _: TemperatureMeasurement<Millicelsius> =
+///     monitor.read_ambient_temperature(&mut mcp9808).unwrap();
+/// assert!(monitor.health().is_healthy());
+/// ```
+#[derive(Debug, Clone)]
+pub struct HealthMonitor {
+    resolution: Resolution,
+    max_jump: Option<Millicelsius>,
+    stuck_threshold: u32,
+    last_temperature: Option<Millicelsius>,
+    repeats: u32,
+    health: SensorHealth,
+}
+
+impl HealthMonitor {
+    /// Creates new monitor for device configured with `Resolution`.
+    /// Readings are stuck after 100 identical readings and may jump by at most
+    /// what 40°C/s allows within one conversion of the `Resolution`,
+    /// from 1.2°C at 0.5°C (30 ms) to 10°C at 0.0625°C (250 ms).
+    /// Readings taken further apart than one conversion need `set_max_jump`.
+    pub fn new(resolution: Resolution) -> Self {
+        HealthMonitor {
+            resolution,
+            max_jump: None,
+            stuck_threshold: DEFAULT_STUCK_THRESHOLD,
+            last_temperature: None,
+            repeats: 0,
+            health: SensorHealth::default(),
+        }
+    }
+
+    /// Change `Resolution` the device is configured with,
+    /// the default maximum jump follows its conversion time
+    pub fn set_resolution(&mut self, resolution: Resolution) {
+        self.resolution = resolution;
+    }
+
+    /// Change maximum plausible temperature change between two consecutive readings,
+    /// replacing the default derived from the `Resolution`
+    pub fn set_max_jump<Unit>(&mut self, max_jump: Unit)
+    where
+        Unit: Into<Millicelsius>,
+    {
+        self.max_jump = Some(max_jump.into());
+    }
+
+    /// Maximum plausible temperature change between two consecutive readings
+    pub fn max_jump(&self) -> Millicelsius {
+        self.max_jump.unwrap_or_else(|| {
+            Millicelsius(DEFAULT_MAX_RATE * self.resolution.conversion_time_ms() as i32)
+        })
+    }
+
+    /// Change number of identical consecutive readings after which the sensor is stuck
+    pub fn set_stuck_threshold(&mut self, readings: u32) {
+        self.stuck_threshold = readings;
+    }
+
+    /// Current `SensorHealth`
+    pub fn health(&self) -> SensorHealth {
+        self.health
+    }
+
+    /// Clear all flags, counters and reading history
+    pub fn reset(&mut self) {
+        self.last_temperature = None;
+        self.repeats = 0;
+        self.health = SensorHealth::default();
+    }
+
+    /// Check plausibility of a temperature reading
+    pub fn check_temperature<Unit>(&mut self, temperature: Unit) -> SensorHealth
+    where
+        Unit: Into<Millicelsius>,
+    {
+        let temperature = temperature.into();
+
+        self.health.out_of_range = temperature < MIN_TEMPERATURE || temperature > MAX_TEMPERATURE;
+
+        let jump = self
+            .last_temperature
+            .map(|last| (i64::from(temperature.0) - i64::from(last.0)).abs())
+            .unwrap_or(0);
+        self.health.implausible_jump =
+            jump > i64::from(self.max_jump().0) || !self.is_aligned(temperature);
+
+        if self.last_temperature == Some(temperature) {
+            self.repeats = self.repeats.saturating_add(1);
+        } else {
+            self.repeats = 1;
+        }
+        self.health.stuck = self.stuck_threshold > 0 && self.repeats >= self.stuck_threshold;
+        self.last_temperature = Some(temperature);
+
+        self.health
+    }
+
+    /// Record the result of a bus transaction
+    pub fn record_transaction<T, Err>(&mut self, result: &Result<T, Err>) {
+        if result.is_ok() {
+            self.health.consecutive_bus_errors = 0;
+        } else {
+            self.health.consecutive_bus_errors =
+                self.health.consecutive_bus_errors.saturating_add(1);
+            self.health.total_bus_errors = self.health.total_bus_errors.saturating_add(1);
+        }
+    }
+
+    /// Read `TemperatureMeasurement` from `MCP9808`, recording the transaction and checking the reading
+    pub fn read_ambient_temperature<I2C, Unit, Err>(
+        &mut self,
+        mcp9808: &mut MCP9808<I2C>,
+    ) -> Result<TemperatureMeasurement<Unit>, Err>
+    where
+        I2C: i2c::WriteRead<Error = Err>,
+        Unit: From<Millicelsius> + TemperatureUnit,
+    {
+        let result = mcp9808.read_ambient_temperature::<Millicelsius, Err>();
+        self.record_transaction(&result);
+        let measurement = result?;
+        self.check_temperature(measurement.temperature);
+        Ok(TemperatureMeasurement {
+            temperature: measurement.temperature.into(),
+            is_critical: measurement.is_critical,
+            is_upper: measurement.is_upper,
+            is_lower: measurement.is_lower,
+        })
+    }
+
+    /// Read Manufacturer ID and Device ID from `MCP9808`, bypassing its register cache,
+    /// and check they match the MCP9808
+    pub fn check_identity<I2C, Err>(&mut self, mcp9808: &mut MCP9808<I2C>) -> Result<bool, Err>
+    where
+        I2C: i2c::WriteRead<Error = Err>,
+    {
+        let manufacturer_id = mcp9808.read_uncached(|mcp9808| mcp9808.read_manufacturer_id());
+        self.record_transaction(&manufacturer_id);
+        let device_information = mcp9808.read_uncached(|mcp9808| mcp9808.read_device_information());
+        self.record_transaction(&device_information);
+
+        let (device_id, _) = device_information?;
+        let valid = manufacturer_id?.is_valid() && device_id.is_valid();
+        self.health.identity_mismatch = !valid;
+        Ok(valid)
+    }
+
+    /// Temperature has no fraction bits finer than the active `Resolution`
+    fn is_aligned(&self, temperature: Millicelsius) -> bool {
+        let step = match self.resolution {
+            Resolution::Deg0_5C => 500,
+            Resolution::Deg0_25C => 250,
+            Resolution::Deg0_125C => 125,
+            Resolution::Deg0_0625C => return true,
+        };
+        temperature.0.rem_euclid(1_000) % step == 0
+    }
+}
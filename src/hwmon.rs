@@ -0,0 +1,195 @@
+//! Linux jc42 hwmon Backend
+//!
+//! When the kernel `jc42` driver is bound to the MCP9808, the sensor is only reachable
+//! through the hwmon sysfs attributes. `Jc42` offers the driver API on top of them.
+
+use crate::{
+    configuration::Hysteresis,
+    temperature::{Millicelsius, TemperatureMeasurement, TemperatureUnit},
+};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Default hwmon class directory
+pub const HWMON_ROOT: &str = "/sys/class/hwmon";
+
+const DRIVER_NAME: &str = "jc42";
+
+/// hwmon backend error
+#[derive(Debug)]
+pub enum Error {
+    /// Attribute could not be read or written
+    Io(io::Error),
+    /// Attribute does not hold a valid value
+    InvalidValue,
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+/// MCP9808 bound to the kernel jc42 driver
+///
+/// # Example
+///
+/// ```no_run
+/// use mcp9808::{
+///     hwmon::{Jc42, HWMON_ROOT},
+///     temperature::{Celsius, TemperatureMeasurement},
+/// };
+///
+/// let mut sensor = Jc42::find(HWMON_ROOT).unwrap().remove(0);
+/// let measurement: TemperatureMeasurement<Celsius> = sensor.read_ambient_temperature().unwrap();
+/// sensor.write_upper_temperature(Celsius(30.0)).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Jc42 {
+    path: PathBuf,
+}
+
+impl Jc42 {
+    /// Creates new backend for the hwmon device directory, e.g. `/sys/class/hwmon/hwmon2`
+    pub fn new<P>(path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Jc42 { path: path.into() }
+    }
+
+    /// All hwmon devices under `root` bound to the jc42 driver, sorted by path
+    pub fn find<P>(root: P) -> Result<Vec<Self>, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let mut devices = Vec::new();
+        for entry in fs::read_dir(root)? {
+            let path = entry?.path();
+            if let Ok(name) = fs::read_to_string(path.join("name")) {
+                if name.trim() == DRIVER_NAME {
+                    devices.push(Jc42::new(path));
+                }
+            }
+        }
+        devices.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(devices)
+    }
+
+    /// hwmon device directory
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read `TemperatureMeasurement` with Temperature `Unit` from `temp1_input` and alarms
+    pub fn read_ambient_temperature<Unit>(&mut self) -> Result<TemperatureMeasurement<Unit>, Error>
+    where
+        Unit: From<Millicelsius> + TemperatureUnit,
+    {
+        Ok(TemperatureMeasurement {
+            temperature: self.read_temperature("temp1_input")?.into(),
+            is_critical: self.read_alarm("temp1_crit_alarm")?,
+            is_upper: self.read_alarm("temp1_max_alarm")?,
+            is_lower: self.read_alarm("temp1_min_alarm")?,
+        })
+    }
+
+    /// Read T_UPPER from `temp1_max`
+    pub fn read_upper_temperature<Unit>(&mut self) -> Result<Unit, Error>
+    where
+        Unit: From<Millicelsius> + TemperatureUnit,
+    {
+        self.read_temperature("temp1_max").map(Unit::from)
+    }
+
+    /// Write T_UPPER to `temp1_max`
+    pub fn write_upper_temperature<Unit>(&mut self, temperature: Unit) -> Result<(), Error>
+    where
+        Unit: Into<Millicelsius> + TemperatureUnit,
+    {
+        self.write_temperature("temp1_max", temperature.into())
+    }
+
+    /// Read T_LOWER from `temp1_min`
+    pub fn read_lower_temperature<Unit>(&mut self) -> Result<Unit, Error>
+    where
+        Unit: From<Millicelsius> + TemperatureUnit,
+    {
+        self.read_temperature("temp1_min").map(Unit::from)
+    }
+
+    /// Write T_LOWER to `temp1_min`
+    pub fn write_lower_temperature<Unit>(&mut self, temperature: Unit) -> Result<(), Error>
+    where
+        Unit: Into<Millicelsius> + TemperatureUnit,
+    {
+        self.write_temperature("temp1_min", temperature.into())
+    }
+
+    /// Read T_CRIT from `temp1_crit`
+    pub fn read_critical_temperature<Unit>(&mut self) -> Result<Unit, Error>
+    where
+        Unit: From<Millicelsius> + TemperatureUnit,
+    {
+        self.read_temperature("temp1_crit").map(Unit::from)
+    }
+
+    /// Write T_CRIT to `temp1_crit`
+    pub fn write_critical_temperature<Unit>(&mut self, temperature: Unit) -> Result<(), Error>
+    where
+        Unit: Into<Millicelsius> + TemperatureUnit,
+    {
+        self.write_temperature("temp1_crit", temperature.into())
+    }
+
+    /// Read `Hysteresis` as the distance of `temp1_crit_hyst` below `temp1_crit`
+    pub fn read_hysteresis(&mut self) -> Result<Hysteresis, Error> {
+        let critical = self.read_temperature("temp1_crit")?;
+        let threshold = self.read_temperature("temp1_crit_hyst")?;
+        match critical.0 - threshold.0 {
+            0 => Ok(Hysteresis::Deg0C),
+            1_500 => Ok(Hysteresis::Deg1_5C),
+            3_000 => Ok(Hysteresis::Deg3_0C),
+            6_000 => Ok(Hysteresis::Deg6_0C),
+            _ => Err(Error::InvalidValue),
+        }
+    }
+
+    /// Write `Hysteresis` to `temp1_crit_hyst`, relative to the current `temp1_crit`
+    pub fn write_hysteresis(&mut self, hysteresis: Hysteresis) -> Result<(), Error> {
+        let critical = self.read_temperature("temp1_crit")?;
+        let hysteresis = match hysteresis {
+            Hysteresis::Deg0C => 0,
+            Hysteresis::Deg1_5C => 1_500,
+            Hysteresis::Deg3_0C => 3_000,
+            Hysteresis::Deg6_0C => 6_000,
+        };
+        self.write_temperature("temp1_crit_hyst", Millicelsius(critical.0 - hysteresis))
+    }
+
+    fn read_attribute(&self, attribute: &str) -> Result<i32, Error> {
+        fs::read_to_string(self.path.join(attribute))?
+            .trim()
+            .parse()
+            .map_err(|_| Error::InvalidValue)
+    }
+
+    fn read_temperature(&self, attribute: &str) -> Result<Millicelsius, Error> {
+        self.read_attribute(attribute).map(Millicelsius)
+    }
+
+    fn read_alarm(&self, attribute: &str) -> Result<bool, Error> {
+        match self.read_attribute(attribute)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(Error::InvalidValue),
+        }
+    }
+
+    fn write_temperature(&self, attribute: &str, temperature: Millicelsius) -> Result<(), Error> {
+        fs::write(self.path.join(attribute), temperature.0.to_string())?;
+        Ok(())
+    }
+}
@@ -51,18 +51,42 @@
 //! assert_eq!(Celsius(-24.063), measurement.temperature);
 //! ```
 
-#![no_std]
+#![cfg_attr(not(feature = "std"), no_std)]
 
-pub use address::SlaveAddress;
+pub use address::{InvalidAddressError, SlaveAddress};
 use embedded_hal as hal;
 use i2c_interface::I2cInterface;
+pub use registers::Register;
 
 mod address;
+pub mod cache;
+pub mod calibration;
+pub mod clock;
 pub mod configuration;
+#[cfg(feature = "std")]
+pub mod datalog;
 pub mod device_id_revision;
+pub mod fan_curve;
+pub mod fault;
+pub mod filter;
+pub mod health;
+#[cfg(feature = "std")]
+pub mod hwmon;
 pub mod manufacturer_id;
+pub mod pid;
+pub mod rate_of_change;
+pub mod replay;
 pub mod resolution;
+pub mod retry;
+#[cfg(feature = "embedded-storage")]
+pub mod settings;
+pub mod simulator;
+pub mod statistics;
+pub mod supervisor;
 pub mod temperature;
+pub mod thermostat;
+pub mod trace;
+pub mod verify;
 
 mod registers;
 
@@ -70,6 +94,12 @@ mod registers;
 pub struct MCP9808<I2C> {
     /// I2C interface
     i2c_interface: I2cInterface<I2C>,
+
+    /// Shadow register cache
+    cache: cache::RegisterCache,
+
+    /// Read back and compare register writes
+    verify: bool,
 }
 
 impl<I2C> MCP9808<I2C> {
@@ -80,6 +110,8 @@ impl<I2C> MCP9808<I2C> {
                 i2c,
                 address: address.into(),
             },
+            cache: cache::RegisterCache::default(),
+            verify: false,
         }
     }
 
@@ -88,6 +120,16 @@ impl<I2C> MCP9808<I2C> {
         self.i2c_interface.address
     }
 
+    /// Shared reference to the `I2C`
+    pub fn i2c(&self) -> &I2C {
+        &self.i2c_interface.i2c
+    }
+
+    /// Mutable reference to the `I2C`
+    pub fn i2c_mut(&mut self) -> &mut I2C {
+        &mut self.i2c_interface.i2c
+    }
+
     /// Release the `I2C`
     pub fn release(self) -> I2C {
         self.i2c_interface.i2c
@@ -11,6 +11,7 @@ type Raw = GenericArray<u8, U2>;
 /// device in order to perform manufacturer-specific
 /// operation.
 #[derive(Debug, PartialEq, PartialOrd, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ManufacturerId(pub u16);
 
 impl ManufacturerId {
@@ -26,14 +27,20 @@ impl From<Raw> for ManufacturerId {
     }
 }
 
+#[cfg(feature = "defmt")]
+impl defmt::Format for ManufacturerId {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "{=u16:#06x}", self.0)
+    }
+}
+
 impl<I2C> MCP9808<I2C> {
     /// Read `ManufacturerId` from `ManufacturerIdRegister`
     pub fn read_manufacturer_id<Err>(&mut self) -> Result<ManufacturerId, Err>
     where
         I2C: i2c::WriteRead<Error = Err>,
     {
-        self.i2c_interface
-            .read_register(Register::ManufacturerIdRegister)
+        self.read_register(Register::ManufacturerIdRegister)
             .map(ManufacturerId::from)
     }
 }
@@ -0,0 +1,185 @@
+//! PID Temperature Controller
+
+use crate::{
+    clock::Clock,
+    hal::{blocking::i2c, PwmPin},
+    temperature::{Millicelsius, TemperatureMeasurement, TemperatureUnit},
+    thermostat::Polarity,
+    MCP9808,
+};
+use num_traits::{NumCast, ToPrimitive};
+
+/// PID controller error
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Error {
+    /// Output limits are not ordered within `0.0..=1.0`, or not a number
+    InvalidOutputLimits,
+}
+
+/// Controller gains, error is expressed in °C and output as duty cycle fraction
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Gains {
+    /// Proportional gain, per °C
+    pub kp: f32,
+
+    /// Integral gain, per °C and second
+    pub ki: f32,
+
+    /// Derivative gain, per °C per second
+    pub kd: f32,
+}
+
+/// PID controller driving duty cycle of a `PwmPin`
+///
+/// The derivative term is computed on measurement instead of error,
+/// so setpoint changes do not cause output spikes.
+/// The integral term is clamped to the output limits to prevent windup.
+pub struct PidController<Pin, Clk> {
+    pin: Pin,
+    clock: Clk,
+    gains: Gains,
+    setpoint: Millicelsius,
+    polarity: Polarity,
+    output_min: f32,
+    output_max: f32,
+    integral: f32,
+    output: f32,
+    last_update: Option<(Millicelsius, u64)>,
+}
+
+impl<Pin, Clk> PidController<Pin, Clk>
+where
+    Pin: PwmPin,
+    Pin::Duty: NumCast + ToPrimitive,
+    Clk: Clock,
+{
+    /// Creates new controller with `setpoint`, `Gains` and `Polarity`.
+    /// Output is limited to the full duty cycle range `0.0..=1.0`.
+    pub fn new<Unit>(pin: Pin, clock: Clk, setpoint: Unit, gains: Gains, polarity: Polarity) -> Self
+    where
+        Unit: Into<Millicelsius>,
+    {
+        PidController {
+            pin,
+            clock,
+            gains,
+            setpoint: setpoint.into(),
+            polarity,
+            output_min: 0.0,
+            output_max: 1.0,
+            integral: 0.0,
+            output: 0.0,
+            last_update: None,
+        }
+    }
+
+    /// Change setpoint
+    pub fn set_setpoint<Unit>(&mut self, setpoint: Unit)
+    where
+        Unit: Into<Millicelsius>,
+    {
+        self.setpoint = setpoint.into();
+    }
+
+    /// Change `Gains`
+    pub fn set_gains(&mut self, gains: Gains) {
+        self.gains = gains;
+    }
+
+    /// Limit output duty cycle fraction to `min..=max`, both within `0.0..=1.0`
+    pub fn set_output_limits(&mut self, min: f32, max: f32) -> Result<(), Error> {
+        let valid = 0.0 <= min && min <= max && max <= 1.0;
+        if !valid {
+            return Err(Error::InvalidOutputLimits);
+        }
+        self.output_min = min;
+        self.output_max = max;
+        self.integral = clamp(self.integral, min, max);
+        Ok(())
+    }
+
+    /// Current setpoint
+    pub fn setpoint(&self) -> Millicelsius {
+        self.setpoint
+    }
+
+    /// Duty cycle fraction applied in the last update
+    pub fn output(&self) -> f32 {
+        self.output
+    }
+
+    /// Clear integral term and measurement history
+    pub fn reset(&mut self) {
+        self.integral = 0.0;
+        self.last_update = None;
+    }
+
+    /// Run a control step with ambient temperature read from `MCP9808`
+    pub fn step<I2C, Err>(&mut self, mcp9808: &mut MCP9808<I2C>) -> Result<f32, Err>
+    where
+        I2C: i2c::WriteRead<Error = Err>,
+    {
+        let measurement: TemperatureMeasurement<Millicelsius> =
+            mcp9808.read_ambient_temperature()?;
+        Ok(self.update(measurement))
+    }
+
+    /// Run a control step with `TemperatureMeasurement` and apply new duty cycle fraction
+    pub fn update<Unit>(&mut self, measurement: TemperatureMeasurement<Unit>) -> f32
+    where
+        Unit: Into<Millicelsius> + TemperatureUnit,
+    {
+        let temperature = measurement.temperature.into();
+        let now = self.clock.now_ms();
+        let direction = match self.polarity {
+            Polarity::Heating => 1.0,
+            Polarity::Cooling => -1.0,
+        };
+        let error = direction * degrees(self.setpoint.0 - temperature.0);
+
+        let mut derivative = 0.0;
+        if let Some((last_temperature, last_time)) = self.last_update {
+            let dt = now.saturating_sub(last_time) as f32 / 1_000.0;
+            if dt > 0.0 {
+                self.integral = clamp(
+                    self.integral + self.gains.ki * error * dt,
+                    self.output_min,
+                    self.output_max,
+                );
+                derivative = -direction * degrees(temperature.0 - last_temperature.0) / dt;
+            }
+        }
+        self.last_update = Some((temperature, now));
+
+        let output = self.gains.kp * error + self.integral + self.gains.kd * derivative;
+        self.output = clamp(output, self.output_min, self.output_max);
+        self.apply();
+        self.output
+    }
+
+    /// Release the PWM pin and clock
+    pub fn release(self) -> (Pin, Clk) {
+        (self.pin, self.clock)
+    }
+
+    fn apply(&mut self) {
+        let max_duty = self.pin.get_max_duty().to_f32().unwrap_or(0.0);
+        if let Some(duty) = NumCast::from(self.output * max_duty) {
+            self.pin.set_duty(duty);
+        }
+    }
+}
+
+fn degrees(millicelsius: i32) -> f32 {
+    millicelsius as f32 / 1_000.0
+}
+
+fn clamp(value: f32, min: f32, max: f32) -> f32 {
+    if value < min {
+        min
+    } else if value > max {
+        max
+    } else {
+        value
+    }
+}
@@ -0,0 +1,205 @@
+//! Rate of Change and Thermal Runaway Detection
+
+use crate::{
+    clock::Clock,
+    hal::blocking::i2c,
+    temperature::{Millicelsius, TemperatureMeasurement, TemperatureUnit},
+    MCP9808,
+};
+use i2c_interface::generic_array::{ArrayLength, GenericArray};
+
+const MILLISECONDS_PER_MINUTE: i64 = 60_000;
+
+/// Temperature rate of change in millicelsius per minute
+#[derive(Debug, PartialOrd, PartialEq, Copy, Clone)]
+pub struct MillicelsiusPerMinute(pub i32);
+
+/// Rate of change estimated over the last `N` measurements
+///
+/// The rate is the slope between the oldest and the newest measurement in the window,
+/// timestamps are taken from a `Clock` when a measurement is added.
+pub struct RateOfChange<Clk, N>
+where
+    N: ArrayLength<(u64, i32)>,
+{
+    clock: Clk,
+    samples: GenericArray<(u64, i32), N>,
+    next: usize,
+    len: usize,
+}
+
+impl<Clk, N> RateOfChange<Clk, N>
+where
+    Clk: Clock,
+    N: ArrayLength<(u64, i32)>,
+{
+    /// Creates new estimator, `N` must be at least 2
+    pub fn new(clock: Clk) -> Self {
+        assert!(N::to_usize() >= 2);
+        RateOfChange {
+            clock,
+            samples: GenericArray::default(),
+            next: 0,
+            len: 0,
+        }
+    }
+
+    /// Add `TemperatureMeasurement` and return estimated rate,
+    /// `None` until two measurements taken at different times are available
+    pub fn update<Unit>(
+        &mut self,
+        measurement: TemperatureMeasurement<Unit>,
+    ) -> Option<MillicelsiusPerMinute>
+    where
+        Unit: Into<Millicelsius> + TemperatureUnit,
+    {
+        let now = self.clock.now_ms();
+        self.push(now, measurement.temperature.into())
+    }
+
+    /// Estimated rate over the current window
+    pub fn rate(&self) -> Option<MillicelsiusPerMinute> {
+        if self.len < 2 {
+            return None;
+        }
+        let capacity = self.samples.len();
+        let newest = self.samples[(self.next + capacity - 1) % capacity];
+        let oldest = self.samples[(self.next + capacity - self.len) % capacity];
+
+        let elapsed = newest.0.saturating_sub(oldest.0) as i64;
+        if elapsed == 0 {
+            return None;
+        }
+        let delta = i64::from(newest.1) - i64::from(oldest.1);
+        Some(MillicelsiusPerMinute(
+            (delta * MILLISECONDS_PER_MINUTE / elapsed) as i32,
+        ))
+    }
+
+    /// Forget all measurements
+    pub fn reset(&mut self) {
+        self.next = 0;
+        self.len = 0;
+    }
+
+    /// Release the clock
+    pub fn release(self) -> Clk {
+        self.clock
+    }
+
+    fn push(&mut self, now: u64, temperature: Millicelsius) -> Option<MillicelsiusPerMinute> {
+        self.samples[self.next] = (now, temperature.0);
+        self.next = (self.next + 1) % self.samples.len();
+        self.len = (self.len + 1).min(self.samples.len());
+        self.rate()
+    }
+}
+
+/// Thermal runaway event
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum RunawayEvent {
+    /// Rate exceeded the threshold for the configured duration
+    Detected {
+        /// Rate at the time of detection
+        rate: MillicelsiusPerMinute,
+        /// Timestamp in milliseconds since which the rate exceeds the threshold
+        since_ms: u64,
+    },
+    /// Rate dropped back to or below the threshold after a runaway was detected
+    Cleared,
+}
+
+/// Thermal runaway detector
+///
+/// Raises `RunawayEvent::Detected` once the rate of change stays above the threshold
+/// for at least the configured duration, and `RunawayEvent::Cleared` when it drops back.
+pub struct RunawayDetector<Clk, N>
+where
+    N: ArrayLength<(u64, i32)>,
+{
+    rate_of_change: RateOfChange<Clk, N>,
+    threshold: MillicelsiusPerMinute,
+    duration_ms: u64,
+    exceeded_since_ms: Option<u64>,
+    detected: bool,
+}
+
+impl<Clk, N> RunawayDetector<Clk, N>
+where
+    Clk: Clock,
+    N: ArrayLength<(u64, i32)>,
+{
+    /// Creates new detector raising an event when rate exceeds `threshold` for `duration_ms`
+    pub fn new(clock: Clk, threshold: MillicelsiusPerMinute, duration_ms: u64) -> Self {
+        RunawayDetector {
+            rate_of_change: RateOfChange::new(clock),
+            threshold,
+            duration_ms,
+            exceeded_since_ms: None,
+            detected: false,
+        }
+    }
+
+    /// Runaway is currently detected
+    pub fn is_runaway(&self) -> bool {
+        self.detected
+    }
+
+    /// Current rate of change estimate
+    pub fn rate(&self) -> Option<MillicelsiusPerMinute> {
+        self.rate_of_change.rate()
+    }
+
+    /// Add `TemperatureMeasurement` read from `MCP9808`
+    pub fn step<I2C, Err>(
+        &mut self,
+        mcp9808: &mut MCP9808<I2C>,
+    ) -> Result<Option<RunawayEvent>, Err>
+    where
+        I2C: i2c::WriteRead<Error = Err>,
+    {
+        let measurement: TemperatureMeasurement<Millicelsius> =
+            mcp9808.read_ambient_temperature()?;
+        Ok(self.update(measurement))
+    }
+
+    /// Add `TemperatureMeasurement` and return an event on state change
+    pub fn update<Unit>(
+        &mut self,
+        measurement: TemperatureMeasurement<Unit>,
+    ) -> Option<RunawayEvent>
+    where
+        Unit: Into<Millicelsius> + TemperatureUnit,
+    {
+        let now = self.rate_of_change.clock.now_ms();
+        match self
+            .rate_of_change
+            .push(now, measurement.temperature.into())
+        {
+            Some(rate) if rate > self.threshold => {
+                let since_ms = *self.exceeded_since_ms.get_or_insert(now);
+                if !self.detected && now.saturating_sub(since_ms) >= self.duration_ms {
+                    self.detected = true;
+                    return Some(RunawayEvent::Detected { rate, since_ms });
+                }
+                None
+            }
+            Some(_) => {
+                self.exceeded_since_ms = None;
+                if self.detected {
+                    self.detected = false;
+                    return Some(RunawayEvent::Cleared);
+                }
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Forget all measurements and clear detection
+    pub fn reset(&mut self) {
+        self.rate_of_change.reset();
+        self.exceeded_since_ms = None;
+        self.detected = false;
+    }
+}
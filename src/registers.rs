@@ -1,4 +1,5 @@
 /// MCP9808 Registers
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Register {
     /// Read/write Configuration register (CONFIG)
     /// The MCP9808 has a 16-bit Configuration register (CONFIG) that allows the user
@@ -39,6 +40,37 @@ pub enum Register {
     ResolutionRegister = 0b1000,
 }
 
+impl Register {
+    /// Register selected by register pointer value
+    pub(crate) fn from_pointer(pointer: u8) -> Option<Self> {
+        match pointer & 0b1111 {
+            0b0001 => Some(Register::ConfigurationRegister),
+            0b0010 => Some(Register::UpperTemperatureRegister),
+            0b0011 => Some(Register::LowerTemperatureRegister),
+            0b0100 => Some(Register::CriticalTemperatureRegister),
+            0b0101 => Some(Register::AmbientTemperatureRegister),
+            0b0110 => Some(Register::ManufacturerIdRegister),
+            0b0111 => Some(Register::DeviceIdRevisionRegister),
+            0b1000 => Some(Register::ResolutionRegister),
+            _ => None,
+        }
+    }
+
+    /// Register name used in the datasheet
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Register::ConfigurationRegister => "CONFIG",
+            Register::UpperTemperatureRegister => "T_UPPER",
+            Register::LowerTemperatureRegister => "T_LOWER",
+            Register::CriticalTemperatureRegister => "T_CRIT",
+            Register::AmbientTemperatureRegister => "T_A",
+            Register::ManufacturerIdRegister => "MANUFACTURER_ID",
+            Register::DeviceIdRevisionRegister => "DEVICE_ID",
+            Register::ResolutionRegister => "RESOLUTION",
+        }
+    }
+}
+
 impl Into<u8> for Register {
     fn into(self) -> u8 {
         self as u8
@@ -0,0 +1,249 @@
+//! Record and Replay of I2C Traces
+//!
+//! A trace is text with one `Record` per line:
+//!
+//! ```text
+//! # operation address pointer data... result
+//! w 18 08 01 ok
+//! r 18 05 c1 90 ok
+//! r 18 06 error
+//! ```
+//!
+//! Values are hexadecimal, a missing pointer is written as `-`,
+//! and everything after `#` is a comment.
+//! Traces are recorded with `trace::TracingI2c` and played back with `ReplayI2c`.
+
+use crate::{
+    hal::blocking::i2c,
+    trace::{Operation, Transaction, MAX_DATA_LEN},
+};
+use core::{fmt, str::FromStr};
+
+/// Trace line could not be parsed
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ParseError;
+
+/// Replay error
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Error {
+    /// Trace line could not be parsed
+    Parse {
+        /// Line number, counting from 1
+        line: usize,
+    },
+    /// Driver issued a different request than recorded
+    Mismatch {
+        /// Line number, counting from 1
+        line: usize,
+        /// Recorded transaction
+        expected: Transaction,
+        /// Issued transaction
+        actual: Transaction,
+    },
+    /// Driver issued more requests than recorded
+    EndOfTrace,
+    /// Recorded transaction failed
+    Recorded {
+        /// Line number, counting from 1
+        line: usize,
+    },
+}
+
+/// `Transaction` in trace format
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Record(pub Transaction);
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let transaction = &self.0;
+        let operation = match transaction.operation {
+            Operation::Write => "w",
+            Operation::Read => "r",
+        };
+        write!(f, "{} {:02x}", operation, transaction.address)?;
+        match transaction.pointer {
+            Some(pointer) => write!(f, " {:02x}", pointer)?,
+            None => write!(f, " -")?,
+        }
+        for byte in transaction.data() {
+            write!(f, " {:02x}", byte)?;
+        }
+        write!(f, " {}", if transaction.ok { "ok" } else { "error" })
+    }
+}
+
+impl FromStr for Record {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut fields = line.split_whitespace();
+        let operation = match fields.next() {
+            Some("w") => Operation::Write,
+            Some("r") => Operation::Read,
+            _ => return Err(ParseError),
+        };
+        let address = fields.next().ok_or(ParseError).and_then(parse_byte)?;
+        let pointer = match fields.next() {
+            Some("-") => None,
+            Some(pointer) => Some(parse_byte(pointer)?),
+            None => return Err(ParseError),
+        };
+
+        let mut data = [0; MAX_DATA_LEN];
+        let mut len = 0;
+        let ok = loop {
+            match fields.next() {
+                Some("ok") => break true,
+                Some("error") => break false,
+                Some(byte) if len < MAX_DATA_LEN => {
+                    data[len] = parse_byte(byte)?;
+                    len += 1;
+                }
+                _ => return Err(ParseError),
+            }
+        };
+        if fields.next().is_some() {
+            return Err(ParseError);
+        }
+
+        Ok(Record(Transaction {
+            operation,
+            address,
+            pointer,
+            data,
+            len,
+            ok,
+        }))
+    }
+}
+
+/// I2C bus serving responses from a recorded trace
+/// and checking the driver issues the recorded requests in order
+///
+/// # Example
+///
+/// ```
+/// use mcp9808::{
+///     replay::ReplayI2c,
+///     resolution::Resolution,
+///     temperature::{Millicelsius, TemperatureMeasurement},
+///     SlaveAddress, MCP9808,
+/// };
+///
+/// let trace = "
+///     w 18 08 01 ok
+///     r 18 05 c1 94 ok # T_A 25.25°C, T_A >= T_CRIT
+/// ";
+/// let mut mcp9808 = MCP9808::new(ReplayI2c::new(trace), SlaveAddress::Default);
+/// mcp9808.write_resolution(Resolution::Deg0_25C).unwrap();
+/// let measurement: TemperatureMeasurement<Millicelsius> =
+///     mcp9808.read_ambient_temperature().unwrap();
+///
+/// assert_eq!(Millicelsius(25_250), measurement.temperature);
+/// assert!(mcp9808.i2c().is_finished());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReplayI2c<'a> {
+    lines: core::str::Lines<'a>,
+    line: usize,
+}
+
+impl<'a> ReplayI2c<'a> {
+    /// Creates new bus replaying `trace`
+    pub fn new(trace: &'a str) -> Self {
+        ReplayI2c {
+            lines: trace.lines(),
+            line: 0,
+        }
+    }
+
+    /// All recorded transactions were replayed
+    pub fn is_finished(&self) -> bool {
+        self.clone().next_record() == Err(Error::EndOfTrace)
+    }
+
+    /// Number of the last replayed line, counting from 1
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    fn next_record(&mut self) -> Result<Transaction, Error> {
+        for line in &mut self.lines {
+            self.line += 1;
+            let content = line.split('#').next().unwrap_or_default().trim();
+            if content.is_empty() {
+                continue;
+            }
+            return content
+                .parse()
+                .map(|Record(transaction)| transaction)
+                .map_err(|_| Error::Parse { line: self.line });
+        }
+        Err(Error::EndOfTrace)
+    }
+
+    /// Next recorded transaction, which must match the request in `actual`
+    fn replay(&mut self, actual: Transaction) -> Result<Transaction, Error> {
+        let expected = self.next_record()?;
+        let same_data = match actual.operation {
+            Operation::Write => expected.data() == actual.data(),
+            Operation::Read => !expected.ok || expected.len == actual.len,
+        };
+        let same_request = expected.operation == actual.operation
+            && expected.address == actual.address
+            && expected.pointer == actual.pointer
+            && same_data;
+        if !same_request {
+            return Err(Error::Mismatch {
+                line: self.line,
+                expected,
+                actual,
+            });
+        }
+        if !expected.ok {
+            return Err(Error::Recorded { line: self.line });
+        }
+        Ok(expected)
+    }
+}
+
+impl<'a> i2c::Write for ReplayI2c<'a> {
+    type Error = Error;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        let (pointer, data) = match bytes.split_first() {
+            Some((pointer, data)) => (Some(*pointer), data),
+            None => (None, bytes),
+        };
+        self.replay(Transaction::new(
+            Operation::Write,
+            address,
+            pointer,
+            data,
+            true,
+        ))
+        .map(|_| ())
+    }
+}
+
+impl<'a> i2c::WriteRead for ReplayI2c<'a> {
+    type Error = Error;
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let mut actual =
+            Transaction::new(Operation::Read, address, bytes.first().cloned(), &[], true);
+        actual.len = buffer.len().min(MAX_DATA_LEN);
+        let recorded = self.replay(actual)?;
+        buffer[..recorded.len].copy_from_slice(recorded.data());
+        Ok(())
+    }
+}
+
+fn parse_byte(field: &str) -> Result<u8, ParseError> {
+    u8::from_str_radix(field, 16).map_err(|_| ParseError)
+}
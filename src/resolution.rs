@@ -3,7 +3,8 @@
 // Clippy warns about `FromPrimitive`, which is not useless
 #![allow(clippy::useless_attribute)]
 
-use crate::{hal::blocking::i2c, MCP9808};
+use crate::{hal::blocking::i2c, temperature::Millicelsius, verify, MCP9808};
+use core::{fmt, str::FromStr};
 use i2c_interface::generic_array::{typenum::consts::U1, GenericArray};
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
@@ -15,17 +16,23 @@ type Raw = GenericArray<u8, U1>;
 /// Device temperature resolution
 /// Used to adjust Temperature Sensor Accuracy and Temperature Conversion Time
 #[derive(Debug, PartialEq, Clone, Copy, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Resolution {
     /// +0.5°C (t_CONV = 30 ms typical)
+    #[cfg_attr(feature = "serde", serde(rename = "0.5C"))]
     Deg0_5C = 0b00,
 
     /// +0.25°C (t_CONV = 65 ms typical)
+    #[cfg_attr(feature = "serde", serde(rename = "0.25C"))]
     Deg0_25C = 0b01,
 
     /// +0.125°C (t_CONV = 130 ms typical)
+    #[cfg_attr(feature = "serde", serde(rename = "0.125C"))]
     Deg0_125C = 0b10,
 
     /// +0.0625°C (power-up default, t_CONV = 250 ms typical)
+    #[cfg_attr(feature = "serde", serde(rename = "0.0625C"))]
     Deg0_0625C = 0b11,
 }
 
@@ -41,23 +48,144 @@ impl Into<Raw> for Resolution {
     }
 }
 
+impl fmt::Display for Resolution {
+    /// Formats the step in °C without unit, e.g. `0.0625`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Resolution::Deg0_5C => "0.5",
+            Resolution::Deg0_25C => "0.25",
+            Resolution::Deg0_125C => "0.125",
+            Resolution::Deg0_0625C => "0.0625",
+        })
+    }
+}
+
+/// Resolution string is not one of `0.5`, `0.25`, `0.125` or `0.0625`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ParseResolutionError;
+
+impl fmt::Display for ParseResolutionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid resolution, expected 0.5, 0.25, 0.125 or 0.0625")
+    }
+}
+
+impl FromStr for Resolution {
+    type Err = ParseResolutionError;
+
+    /// Parses the step in °C as formatted by `Display`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcp9808::resolution::Resolution;
+    ///
+    /// assert_eq!(Ok(Resolution::Deg0_125C), "0.125".parse());
+    /// assert_eq!("0.0625", Resolution::Deg0_0625C.to_string());
+    /// ```
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "0.5" => Ok(Resolution::Deg0_5C),
+            "0.25" => Ok(Resolution::Deg0_25C),
+            "0.125" => Ok(Resolution::Deg0_125C),
+            "0.0625" => Ok(Resolution::Deg0_0625C),
+            _ => Err(ParseResolutionError),
+        }
+    }
+}
+
+impl Resolution {
+    /// Temperature rounded to this resolution, displayed with matching precision
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcp9808::{resolution::Resolution, temperature::Celsius};
+    ///
+    /// assert_eq!("-24.0625 °C", Resolution::Deg0_0625C.display(Celsius(-24.063)).to_string());
+    /// assert_eq!("-24.0 °C", Resolution::Deg0_5C.display(Celsius(-24.063)).to_string());
+    /// ```
+    pub fn display<Unit>(self, temperature: Unit) -> QuantizedTemperature
+    where
+        Unit: Into<Millicelsius>,
+    {
+        let fraction_bits = self.fraction_bits();
+        let scaled = i64::from(temperature.into().0) << fraction_bits;
+        let steps = if scaled < 0 {
+            (scaled - 500) / 1_000
+        } else {
+            (scaled + 500) / 1_000
+        };
+        QuantizedTemperature {
+            steps,
+            fraction_bits,
+        }
+    }
+
+    /// Typical temperature conversion time t_CONV in milliseconds
+    pub fn conversion_time_ms(self) -> u32 {
+        match self {
+            Resolution::Deg0_5C => 30,
+            Resolution::Deg0_25C => 65,
+            Resolution::Deg0_125C => 130,
+            Resolution::Deg0_0625C => 250,
+        }
+    }
+
+    /// Number of binary fraction digits of a temperature at this resolution
+    fn fraction_bits(self) -> u32 {
+        match self {
+            Resolution::Deg0_5C => 1,
+            Resolution::Deg0_25C => 2,
+            Resolution::Deg0_125C => 3,
+            Resolution::Deg0_0625C => 4,
+        }
+    }
+}
+
+/// Temperature rounded to a `Resolution` step, see `Resolution::display`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct QuantizedTemperature {
+    steps: i64,
+    fraction_bits: u32,
+}
+
+impl fmt::Display for QuantizedTemperature {
+    /// Formats with one decimal digit per fraction bit, e.g. `-24.0625 °C` at 0.0625°C
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let magnitude = self.steps.abs();
+        let whole = magnitude >> self.fraction_bits;
+        let fraction_steps = magnitude & ((1 << self.fraction_bits) - 1);
+        let fraction = (fraction_steps * 10_i64.pow(self.fraction_bits)) >> self.fraction_bits;
+        write!(
+            f,
+            "{}{}.{:0width$} °C",
+            if self.steps < 0 { "-" } else { "" },
+            whole,
+            fraction,
+            width = self.fraction_bits as usize
+        )
+    }
+}
+
 impl<I2C> MCP9808<I2C> {
     /// Read `Resolution` from `ResolutionRegister`
     pub fn read_resolution<Err>(&mut self) -> Result<Resolution, Err>
     where
         I2C: i2c::WriteRead<Error = Err>,
     {
-        self.i2c_interface
-            .read_register(Register::ResolutionRegister)
+        self.read_register(Register::ResolutionRegister)
             .map(Resolution::from)
     }
 
     /// Write `Resolution` to `ResolutionRegister`
-    pub fn write_resolution<Err>(&mut self, resolution: Resolution) -> Result<(), Err>
+    pub fn write_resolution<Err>(
+        &mut self,
+        resolution: Resolution,
+    ) -> Result<(), verify::Error<Err>>
     where
-        I2C: i2c::Write<Error = Err>,
+        I2C: i2c::Write<Error = Err> + i2c::WriteRead<Error = Err>,
     {
-        self.i2c_interface
-            .write_register(Register::ResolutionRegister, resolution.into())
+        self.write_register(Register::ResolutionRegister, resolution.into())
     }
 }
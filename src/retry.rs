@@ -0,0 +1,180 @@
+//! Retry Policy for Unreliable I2C Buses
+
+use crate::hal::blocking::{delay::DelayUs, i2c};
+
+/// Number of attempts and backoff between them
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Attempts per transaction including the first one, at least 1
+    pub max_attempts: u8,
+
+    /// Delay in microseconds before the first retry
+    pub initial_backoff_us: u32,
+
+    /// Upper bound of the delay, doubled after every retry
+    pub max_backoff_us: u32,
+
+    /// Total delay in microseconds after which a transaction is not retried anymore
+    pub timeout_us: u32,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, backing off from 100μs to 10ms, within 50ms
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff_us: 100,
+            max_backoff_us: 10_000,
+            timeout_us: 50_000,
+        }
+    }
+}
+
+/// I2C bus retrying failed transactions according to a `RetryPolicy`
+///
+/// Wrapping the bus passed to `MCP9808` retries all register reads and writes.
+/// Only errors accepted by the `is_retryable` predicate are retried.
+///
+/// # Example
+///
+/// ```
+/// # use embedded_hal::blocking::delay::DelayUs;
+/// use mcp9808::{
+///     retry::{RetryI2c, RetryPolicy},
+///     simulator::Simulator,
+///     temperature::{Millicelsius, TemperatureMeasurement},
+///     SlaveAddress, MCP9808,
+/// };
+///
+/// # struct Delay;
+/// # impl DelayUs<u32> for Delay {
+/// #     fn delay_us(&mut self, _: u32) {}
+/// # }
+/// #
+/// let bus = RetryI2c::new(
+///     Simulator::new(SlaveAddress::Default),
+///     Delay,
+///     RetryPolicy::default(),
+///     |_: &_| true,
+/// );
+/// let mut mcp9808 = MCP9808::new(bus, SlaveAddress::Default);
+/// let _: TemperatureMeasurement<Millicelsius> = mcp9808.read_ambient_temperature().unwrap();
+/// assert_eq!(0, mcp9808.i2c().retry_count());
+/// ```
+pub struct RetryI2c<I2C, D, F> {
+    i2c: I2C,
+    delay: D,
+    policy: RetryPolicy,
+    is_retryable: F,
+    retry_count: u32,
+    failure_count: u32,
+}
+
+impl<I2C, D, F> RetryI2c<I2C, D, F> {
+    /// Creates new bus retrying errors of `i2c` accepted by `is_retryable`
+    pub fn new(i2c: I2C, delay: D, policy: RetryPolicy, is_retryable: F) -> Self {
+        RetryI2c {
+            i2c,
+            delay,
+            policy,
+            is_retryable,
+            retry_count: 0,
+            failure_count: 0,
+        }
+    }
+
+    /// Current `RetryPolicy`
+    pub fn policy(&self) -> RetryPolicy {
+        self.policy
+    }
+
+    /// Change `RetryPolicy`
+    pub fn set_policy(&mut self, policy: RetryPolicy) {
+        self.policy = policy;
+    }
+
+    /// Number of retried attempts
+    pub fn retry_count(&self) -> u32 {
+        self.retry_count
+    }
+
+    /// Number of transactions that failed after all retries or with a non-retryable error
+    pub fn failure_count(&self) -> u32 {
+        self.failure_count
+    }
+
+    /// Clear retry and failure counters
+    pub fn reset_counters(&mut self) {
+        self.retry_count = 0;
+        self.failure_count = 0;
+    }
+
+    /// Release the `I2C` and delay provider
+    pub fn release(self) -> (I2C, D) {
+        (self.i2c, self.delay)
+    }
+}
+
+impl<I2C, D, F> RetryI2c<I2C, D, F>
+where
+    D: DelayUs<u32>,
+{
+    fn retry<Err, T>(&mut self, mut transaction: T) -> Result<(), Err>
+    where
+        F: Fn(&Err) -> bool,
+        T: FnMut(&mut I2C) -> Result<(), Err>,
+    {
+        let mut attempt = 1;
+        let mut backoff = self.policy.initial_backoff_us;
+        let mut waited: u32 = 0;
+        loop {
+            let error = match transaction(&mut self.i2c) {
+                Ok(()) => return Ok(()),
+                Err(error) => error,
+            };
+            let exhausted = attempt >= self.policy.max_attempts
+                || waited.saturating_add(backoff) > self.policy.timeout_us;
+            if exhausted || !(self.is_retryable)(&error) {
+                self.failure_count = self.failure_count.saturating_add(1);
+                return Err(error);
+            }
+
+            self.delay.delay_us(backoff);
+            waited = waited.saturating_add(backoff);
+            backoff = backoff.saturating_mul(2).min(self.policy.max_backoff_us);
+            attempt += 1;
+            self.retry_count = self.retry_count.saturating_add(1);
+        }
+    }
+}
+
+impl<I2C, D, F, Err> i2c::Write for RetryI2c<I2C, D, F>
+where
+    I2C: i2c::Write<Error = Err>,
+    D: DelayUs<u32>,
+    F: Fn(&Err) -> bool,
+{
+    type Error = Err;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.retry(|i2c| i2c.write(address, bytes))
+    }
+}
+
+impl<I2C, D, F, Err> i2c::WriteRead for RetryI2c<I2C, D, F>
+where
+    I2C: i2c::WriteRead<Error = Err>,
+    D: DelayUs<u32>,
+    F: Fn(&Err) -> bool,
+{
+    type Error = Err;
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.retry(|i2c| i2c.write_read(address, bytes, buffer))
+    }
+}
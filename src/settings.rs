@@ -0,0 +1,251 @@
+//! Sensor Settings Persistence
+//!
+//! `SensorSettings` are stored as a fixed-size, versioned record protected by CRC-32:
+//!
+//! | Offset | Size | Content                                    |
+//! |--------|------|--------------------------------------------|
+//! | 0      | 2    | Magic `"M9"`                               |
+//! | 2      | 1    | Format version                             |
+//! | 3      | 1    | `Resolution`                               |
+//! | 4      | 2    | `Configuration` register value             |
+//! | 6      | 4    | T_UPPER in millicelsius, little-endian     |
+//! | 10     | 4    | T_LOWER in millicelsius, little-endian     |
+//! | 14     | 4    | T_CRIT in millicelsius, little-endian      |
+//! | 18     | 4    | Calibration offset in millicelsius         |
+//! | 22     | 4    | Calibration gain in parts per million      |
+//! | 26     | 2    | Reserved, zero                             |
+//! | 28     | 4    | CRC-32 of bytes 0 to 27, little-endian     |
+//!
+//! Only a `LinearCalibration` is stored. A `TableCalibration` holds a type-level number
+//! of points that does not fit a fixed-size record; store its points in a record of their own.
+
+use crate::{
+    calibration::LinearCalibration,
+    configuration::Configuration,
+    hal::blocking::i2c,
+    resolution::Resolution,
+    supervisor::{DesiredState, Supervisor},
+    temperature::Millicelsius,
+    verify, MCP9808,
+};
+use embedded_storage::nor_flash::{NorFlash, ReadNorFlash};
+use i2c_interface::generic_array::{typenum::consts::U2, GenericArray};
+use num_traits::FromPrimitive;
+
+/// Size of a serialized `SensorSettings` record in bytes
+pub const SIZE: usize = 32;
+
+/// Current format version
+pub const VERSION: u8 = 1;
+
+const MAGIC: [u8; 2] = *b"M9";
+const CRC_OFFSET: usize = 28;
+
+type Raw = GenericArray<u8, U2>;
+
+/// Error decoding a serialized `SensorSettings` record
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DecodeError {
+    /// Record is erased flash
+    Blank,
+    /// Record does not start with the magic bytes
+    InvalidMagic,
+    /// Record was written with an unknown format version
+    UnsupportedVersion(u8),
+    /// Record checksum does not match its content
+    CrcMismatch,
+    /// Record holds a value out of range
+    InvalidValue,
+}
+
+/// Error loading or saving `SensorSettings`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Error<StorageError> {
+    /// Flash operation failed
+    Storage(StorageError),
+    /// Offset is not aligned to the read or erase size,
+    /// or the record size is not a multiple of the read or write size
+    Misaligned,
+    /// Stored record is invalid
+    Decode(DecodeError),
+}
+
+/// Sensor settings surviving reboots
+///
+/// # Example
+///
+/// ```
+/// use mcp9808::{
+///     calibration::LinearCalibration, settings::SensorSettings, temperature::Millicelsius,
+/// };
+///
+/// let settings = SensorSettings {
+///     critical_temperature: Millicelsius(85_000),
+///     calibration: LinearCalibration::from_offset(Millicelsius(-250)),
+///     ..SensorSettings::default()
+/// };
+/// let bytes = settings.to_bytes();
+/// assert_eq!(Ok(settings), SensorSettings::from_bytes(&bytes));
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SensorSettings {
+    /// Device `Configuration`
+    pub configuration: Configuration,
+
+    /// T_UPPER limit
+    pub upper_temperature: Millicelsius,
+
+    /// T_LOWER limit
+    pub lower_temperature: Millicelsius,
+
+    /// T_CRIT limit
+    pub critical_temperature: Millicelsius,
+
+    /// Measurement `Resolution`
+    pub resolution: Resolution,
+
+    /// Temperature calibration
+    pub calibration: LinearCalibration,
+}
+
+impl Default for SensorSettings {
+    /// Power-on default device state without calibration
+    fn default() -> Self {
+        SensorSettings::from(DesiredState::default())
+    }
+}
+
+impl From<DesiredState> for SensorSettings {
+    fn from(state: DesiredState) -> Self {
+        SensorSettings {
+            configuration: state.configuration,
+            upper_temperature: state.upper_temperature,
+            lower_temperature: state.lower_temperature,
+            critical_temperature: state.critical_temperature,
+            resolution: state.resolution,
+            calibration: LinearCalibration::default(),
+        }
+    }
+}
+
+impl SensorSettings {
+    /// Device part of the settings, to be maintained by a `Supervisor`
+    pub fn desired_state(&self) -> DesiredState {
+        DesiredState {
+            configuration: self.configuration,
+            upper_temperature: self.upper_temperature,
+            lower_temperature: self.lower_temperature,
+            critical_temperature: self.critical_temperature,
+            resolution: self.resolution,
+        }
+    }
+
+    /// Write device part of the settings to `MCP9808`
+    pub fn apply<I2C, Err>(&self, mcp9808: &mut MCP9808<I2C>) -> Result<(), verify::Error<Err>>
+    where
+        I2C: i2c::Write<Error = Err> + i2c::WriteRead<Error = Err>,
+    {
+        Supervisor::new(self.desired_state()).apply(mcp9808)
+    }
+
+    /// Serialize settings into a record
+    pub fn to_bytes(&self) -> [u8; SIZE] {
+        let mut bytes = [0; SIZE];
+        bytes[0..2].copy_from_slice(&MAGIC);
+        bytes[2] = VERSION;
+        bytes[3] = self.resolution as u8;
+        let configuration: Raw = self.configuration.into();
+        bytes[4..6].copy_from_slice(&configuration);
+        bytes[6..10].copy_from_slice(&self.upper_temperature.0.to_le_bytes());
+        bytes[10..14].copy_from_slice(&self.lower_temperature.0.to_le_bytes());
+        bytes[14..18].copy_from_slice(&self.critical_temperature.0.to_le_bytes());
+        bytes[18..22].copy_from_slice(&self.calibration.offset().0.to_le_bytes());
+        bytes[22..26].copy_from_slice(&self.calibration.gain().to_le_bytes());
+        let crc = crc32(&bytes[..CRC_OFFSET]);
+        bytes[CRC_OFFSET..].copy_from_slice(&crc.to_le_bytes());
+        bytes
+    }
+
+    /// Deserialize settings from a record
+    pub fn from_bytes(bytes: &[u8; SIZE]) -> Result<Self, DecodeError> {
+        if bytes.iter().all(|&byte| byte == 0xFF) {
+            return Err(DecodeError::Blank);
+        }
+        if bytes[0..2] != MAGIC {
+            return Err(DecodeError::InvalidMagic);
+        }
+        if bytes[2] != VERSION {
+            return Err(DecodeError::UnsupportedVersion(bytes[2]));
+        }
+        if crc32(&bytes[..CRC_OFFSET]) != read_i32(bytes, CRC_OFFSET) as u32 {
+            return Err(DecodeError::CrcMismatch);
+        }
+
+        let resolution = Resolution::from_u8(bytes[3]).ok_or(DecodeError::InvalidValue)?;
+        let calibration =
+            LinearCalibration::new(Millicelsius(read_i32(bytes, 18)), read_i32(bytes, 22))
+                .map_err(|_| DecodeError::InvalidValue)?;
+        Ok(SensorSettings {
+            configuration: Raw::clone_from_slice(&bytes[4..6]).into(),
+            upper_temperature: Millicelsius(read_i32(bytes, 6)),
+            lower_temperature: Millicelsius(read_i32(bytes, 10)),
+            critical_temperature: Millicelsius(read_i32(bytes, 14)),
+            resolution,
+            calibration,
+        })
+    }
+
+    /// Load settings from flash at `offset`, aligned to the read size
+    pub fn load<Flash>(flash: &mut Flash, offset: u32) -> Result<Self, Error<Flash::Error>>
+    where
+        Flash: ReadNorFlash,
+    {
+        let read_size = Flash::READ_SIZE as u32;
+        let aligned = offset / read_size * read_size == offset
+            && SIZE / Flash::READ_SIZE * Flash::READ_SIZE == SIZE;
+        if !aligned {
+            return Err(Error::Misaligned);
+        }
+        let mut bytes = [0; SIZE];
+        flash.read(offset, &mut bytes).map_err(Error::Storage)?;
+        SensorSettings::from_bytes(&bytes).map_err(Error::Decode)
+    }
+
+    /// Erase flash at `offset`, aligned to the erase size, and save settings there
+    pub fn save<Flash>(&self, flash: &mut Flash, offset: u32) -> Result<(), Error<Flash::Error>>
+    where
+        Flash: NorFlash,
+    {
+        let erase_size = Flash::ERASE_SIZE as u32;
+        let aligned = offset / erase_size * erase_size == offset
+            && SIZE / Flash::WRITE_SIZE * Flash::WRITE_SIZE == SIZE;
+        if !aligned {
+            return Err(Error::Misaligned);
+        }
+        let erased_sectors = (SIZE as u32 - 1) / erase_size + 1;
+        let erase_end = offset + erased_sectors * erase_size;
+        flash.erase(offset, erase_end).map_err(Error::Storage)?;
+        flash
+            .write(offset, &self.to_bytes())
+            .map_err(Error::Storage)
+    }
+}
+
+fn read_i32(bytes: &[u8; SIZE], offset: usize) -> i32 {
+    let mut value = [0; 4];
+    value.copy_from_slice(&bytes[offset..offset + 4]);
+    i32::from_le_bytes(value)
+}
+
+/// CRC-32 (IEEE 802.3)
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0, |crc, &byte| {
+        (0..8).fold(crc ^ u32::from(byte), |crc, _| {
+            if crc & 1 == 1 {
+                crc >> 1 ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
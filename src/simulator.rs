@@ -0,0 +1,207 @@
+//! Simulated Device
+//!
+//! Register level model of the MCP9808 behind an I2C bus,
+//! used to exercise the driver and the components built on it without hardware.
+
+use crate::{
+    hal::blocking::i2c, registers::Register, resolution::Resolution, temperature::Millicelsius,
+    SlaveAddress,
+};
+use i2c_interface::generic_array::{typenum::consts::U2, GenericArray};
+
+const MANUFACTURER_ID: [u8; 2] = [0x00, 0x54];
+const DEVICE_ID_REVISION: [u8; 2] = [0x04, 0x00];
+const SHUTDOWN_BIT: u8 = 1;
+const CRITICAL_LOCK_BIT: u8 = 1 << 7;
+const WINDOW_LOCK_BIT: u8 = 1 << 6;
+const INTERRUPT_CLEAR_BIT: u8 = 1 << 5;
+const ALERT_STATUS_BIT: u8 = 1 << 4;
+const LIMIT_MASK: [u8; 2] = [0b0001_1111, 0b1111_1100];
+
+const CONFIGURATION: usize = Register::ConfigurationRegister as usize;
+const UPPER_TEMPERATURE: usize = Register::UpperTemperatureRegister as usize;
+const LOWER_TEMPERATURE: usize = Register::LowerTemperatureRegister as usize;
+const CRITICAL_TEMPERATURE: usize = Register::CriticalTemperatureRegister as usize;
+const AMBIENT_TEMPERATURE: usize = Register::AmbientTemperatureRegister as usize;
+const RESOLUTION: usize = Register::ResolutionRegister as usize;
+
+type Raw = GenericArray<u8, U2>;
+
+/// Simulated bus error
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Error {
+    /// No device acknowledged the address
+    AddressNack,
+}
+
+/// Simulated MCP9808 implementing blocking I2C traits
+///
+/// # Example
+///
+/// ```
+/// use mcp9808::{
+///     simulator::Simulator,
+///     temperature::{Millicelsius, TemperatureMeasurement},
+///     SlaveAddress, MCP9808,
+/// };
+///
+/// let mut simulator = Simulator::new(SlaveAddress::Default);
+/// simulator.set_ambient_temperature(Millicelsius(21_500));
+///
+/// let mut mcp9808 = MCP9808::new(simulator, SlaveAddress::Default);
+/// let measurement: TemperatureMeasurement<Millicelsius> =
+///     mcp9808.read_ambient_temperature().unwrap();
+/// assert_eq!(Millicelsius(21_500), measurement.temperature);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Simulator {
+    address: u8,
+    pointer: u8,
+    registers: [[u8; 2]; 9],
+    ambient_temperature: Millicelsius,
+}
+
+impl Simulator {
+    /// Creates new simulated device responding at `SlaveAddress` with power-on default registers
+    pub fn new(address: SlaveAddress) -> Self {
+        Simulator {
+            address: address.into(),
+            pointer: 0,
+            registers: power_on_registers(),
+            ambient_temperature: Millicelsius(0),
+        }
+    }
+
+    /// Restore power-on default registers, as after a brown-out
+    pub fn power_on_reset(&mut self) {
+        self.pointer = 0;
+        self.registers = power_on_registers();
+    }
+
+    /// Set temperature measured by the simulated device
+    pub fn set_ambient_temperature<Unit>(&mut self, temperature: Unit)
+    where
+        Unit: Into<Millicelsius>,
+    {
+        self.ambient_temperature = temperature.into();
+    }
+
+    /// Temperature measured by the simulated device
+    pub fn ambient_temperature(&self) -> Millicelsius {
+        self.ambient_temperature
+    }
+
+    fn limit(&self, register: usize) -> Millicelsius {
+        Raw::from(self.registers[register]).into()
+    }
+
+    fn ambient_temperature_register(&self) -> [u8; 2] {
+        let resolution_mask = match self.registers[RESOLUTION][0] & 0b11 {
+            0b00 => 0b1111_1000,
+            0b01 => 0b1111_1100,
+            0b10 => 0b1111_1110,
+            _ => 0b1111_1111,
+        };
+        let temperature = self.ambient_temperature;
+        let raw: Raw = temperature.into();
+        let mut msb = raw[0];
+        if temperature >= self.limit(CRITICAL_TEMPERATURE) {
+            msb |= 1 << 7;
+        }
+        if temperature > self.limit(UPPER_TEMPERATURE) {
+            msb |= 1 << 6;
+        }
+        if temperature < self.limit(LOWER_TEMPERATURE) {
+            msb |= 1 << 5;
+        }
+        [msb, raw[1] & resolution_mask]
+    }
+
+    fn store(&mut self, data: &[u8]) {
+        let pointer = self.pointer as usize;
+        let mut value = match self.registers.get(pointer) {
+            Some(value) => *value,
+            None => return,
+        };
+        for (byte, data) in value.iter_mut().zip(data) {
+            *byte = *data;
+        }
+
+        let locks = self.registers[CONFIGURATION][1];
+        self.registers[pointer] = match pointer {
+            CONFIGURATION => self.configuration_after_write(value),
+            UPPER_TEMPERATURE | LOWER_TEMPERATURE if locks & WINDOW_LOCK_BIT != 0 => return,
+            CRITICAL_TEMPERATURE if locks & CRITICAL_LOCK_BIT != 0 => return,
+            UPPER_TEMPERATURE | LOWER_TEMPERATURE | CRITICAL_TEMPERATURE => {
+                [value[0] & LIMIT_MASK[0], value[1] & LIMIT_MASK[1]]
+            }
+            RESOLUTION => [value[0] & 0b11, 0],
+            _ => return,
+        };
+    }
+
+    /// While locked, only the lock bits can be set and shutdown can be cleared
+    fn configuration_after_write(&self, value: [u8; 2]) -> [u8; 2] {
+        let current = self.registers[CONFIGURATION];
+        if current[1] & (CRITICAL_LOCK_BIT | WINDOW_LOCK_BIT) == 0 {
+            return [
+                value[0] & 0b111,
+                value[1] & !(INTERRUPT_CLEAR_BIT | ALERT_STATUS_BIT),
+            ];
+        }
+        [
+            current[0] & (value[0] | !SHUTDOWN_BIT),
+            current[1] | value[1] & (CRITICAL_LOCK_BIT | WINDOW_LOCK_BIT),
+        ]
+    }
+
+    fn load(&self, buffer: &mut [u8]) {
+        let value = match self.pointer as usize {
+            AMBIENT_TEMPERATURE => self.ambient_temperature_register(),
+            pointer => self.registers.get(pointer).cloned().unwrap_or_default(),
+        };
+        for (byte, value) in buffer.iter_mut().zip(value.iter()) {
+            *byte = *value;
+        }
+    }
+}
+
+fn power_on_registers() -> [[u8; 2]; 9] {
+    let mut registers = [[0; 2]; 9];
+    registers[Register::ManufacturerIdRegister as usize] = MANUFACTURER_ID;
+    registers[Register::DeviceIdRevisionRegister as usize] = DEVICE_ID_REVISION;
+    registers[RESOLUTION] = [Resolution::Deg0_0625C as u8, 0];
+    registers
+}
+
+impl i2c::Write for Simulator {
+    type Error = Error;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        if address != self.address {
+            return Err(Error::AddressNack);
+        }
+        if let Some((pointer, data)) = bytes.split_first() {
+            self.pointer = pointer & 0b1111;
+            if !data.is_empty() {
+                self.store(data);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl i2c::WriteRead for Simulator {
+    type Error = Error;
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        i2c::Write::write(self, address, bytes)?;
+        self.load(buffer);
+        Ok(())
+    }
+}
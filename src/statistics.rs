@@ -0,0 +1,151 @@
+//! Running Temperature Statistics
+
+use crate::temperature::Millicelsius;
+
+/// Fixed-point fraction bits used for the running mean
+const MEAN_FRACTION_BITS: u32 = 16;
+
+/// Extreme temperature with the time it was observed at
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Extreme {
+    /// Extreme temperature
+    pub temperature: Millicelsius,
+
+    /// Timestamp in milliseconds of the sample
+    pub timestamp_ms: u64,
+}
+
+/// Running temperature statistics accumulated with Welford's algorithm in fixed-point
+///
+/// # Example
+///
+/// ```
+/// use mcp9808::{statistics::TemperatureStats, temperature::Millicelsius};
+///
+/// let mut stats = TemperatureStats::new();
+/// stats.add(Millicelsius(20_000), 0);
+/// stats.add(Millicelsius(22_000), 1_000);
+/// stats.add(Millicelsius(24_000), 2_000);
+///
+/// assert_eq!(Some(Millicelsius(22_000)), stats.mean());
+/// assert_eq!(Some(Millicelsius(24_000)), stats.max().map(|max| max.temperature));
+/// assert_eq!(Some(0), stats.min().map(|min| min.timestamp_ms));
+///
+/// stats.reset();
+/// assert_eq!(None, stats.mean());
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct TemperatureStats {
+    count: u32,
+    min: Option<Extreme>,
+    max: Option<Extreme>,
+    mean: i64,
+    sum_of_squares: i128,
+}
+
+impl TemperatureStats {
+    /// Creates new empty statistics
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add temperature sample taken at `timestamp_ms`
+    pub fn add<Unit>(&mut self, temperature: Unit, timestamp_ms: u64)
+    where
+        Unit: Into<Millicelsius>,
+    {
+        let temperature = temperature.into();
+        let sample = i64::from(temperature.0) << MEAN_FRACTION_BITS;
+
+        self.count = self.count.saturating_add(1);
+        let delta = sample - self.mean;
+        self.mean += delta / i64::from(self.count);
+        self.sum_of_squares += i128::from(delta) * i128::from(sample - self.mean);
+
+        let extreme = Extreme {
+            temperature,
+            timestamp_ms,
+        };
+        match self.min {
+            Some(min) if min.temperature <= temperature => {}
+            _ => self.min = Some(extreme),
+        }
+        match self.max {
+            Some(max) if max.temperature >= temperature => {}
+            _ => self.max = Some(extreme),
+        }
+    }
+
+    /// Clear all samples, to start a new reporting period
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Number of samples
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Lowest temperature, first occurrence wins
+    pub fn min(&self) -> Option<Extreme> {
+        self.min
+    }
+
+    /// Highest temperature, first occurrence wins
+    pub fn max(&self) -> Option<Extreme> {
+        self.max
+    }
+
+    /// Mean temperature
+    pub fn mean(&self) -> Option<Millicelsius> {
+        if self.count == 0 {
+            return None;
+        }
+        let half = 1 << (MEAN_FRACTION_BITS - 1);
+        Some(Millicelsius(
+            ((self.mean + half) >> MEAN_FRACTION_BITS) as i32,
+        ))
+    }
+
+    /// Population variance in millicelsius squared
+    pub fn variance(&self) -> Option<u64> {
+        if self.count == 0 {
+            return None;
+        }
+        Some(self.scaled_variance(u64::from(self.count)))
+    }
+
+    /// Sample variance in millicelsius squared, requires at least two samples
+    pub fn sample_variance(&self) -> Option<u64> {
+        if self.count < 2 {
+            return None;
+        }
+        Some(self.scaled_variance(u64::from(self.count) - 1))
+    }
+
+    /// Population standard deviation
+    pub fn standard_deviation(&self) -> Option<Millicelsius> {
+        self.variance()
+            .map(|variance| Millicelsius(integer_square_root(variance) as i32))
+    }
+
+    fn scaled_variance(&self, divisor: u64) -> u64 {
+        let variance = self.sum_of_squares.max(0) / i128::from(divisor);
+        let half = 1 << (2 * MEAN_FRACTION_BITS - 1);
+        ((variance + half) >> (2 * MEAN_FRACTION_BITS)) as u64
+    }
+}
+
+/// Largest integer not greater than the square root of `value`
+fn integer_square_root(value: u64) -> u64 {
+    if value < 2 {
+        return value;
+    }
+    let mut root = value / 2 + 1;
+    let mut next = (root + value / root) / 2;
+    while next < root {
+        root = next;
+        next = (root + value / root) / 2;
+    }
+    root
+}
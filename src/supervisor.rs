@@ -0,0 +1,182 @@
+//! Power-on Reset Supervisor
+
+use crate::{
+    configuration::Configuration, hal::blocking::i2c, resolution::Resolution,
+    temperature::Millicelsius, verify, MCP9808,
+};
+/// Device state maintained by `Supervisor`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DesiredState {
+    /// Device `Configuration`
+    pub configuration: Configuration,
+
+    /// T_UPPER limit
+    pub upper_temperature: Millicelsius,
+
+    /// T_LOWER limit
+    pub lower_temperature: Millicelsius,
+
+    /// T_CRIT limit
+    pub critical_temperature: Millicelsius,
+
+    /// Measurement `Resolution`
+    pub resolution: Resolution,
+}
+
+impl Default for DesiredState {
+    /// Power-on default device state
+    fn default() -> Self {
+        DesiredState {
+            configuration: Configuration::default(),
+            upper_temperature: Millicelsius(0),
+            lower_temperature: Millicelsius(0),
+            critical_temperature: Millicelsius(0),
+            resolution: Resolution::Deg0_0625C,
+        }
+    }
+}
+
+impl DesiredState {
+    /// State as read back from the device, without status bits and finer than limit resolution
+    fn comparable(self) -> Self {
+        DesiredState {
+            configuration: self.configuration.without_status(),
+            upper_temperature: self.upper_temperature.limit_resolution(),
+            lower_temperature: self.lower_temperature.limit_resolution(),
+            critical_temperature: self.critical_temperature.limit_resolution(),
+            resolution: self.resolution,
+        }
+    }
+}
+
+/// Result of a `Supervisor` check
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Status {
+    /// Device state matches the desired state
+    InSync,
+    /// Device reverted to power-on defaults, desired state was re-applied
+    ResetDetected,
+    /// Device state differs from the desired state, desired state was re-applied
+    Mismatch,
+}
+
+/// Supervisor detecting device resets and re-applying the desired state
+///
+/// # Example
+///
+/// ```
+/// use mcp9808::{
+///     resolution::Resolution,
+///     simulator::Simulator,
+///     supervisor::{DesiredState, Status, Supervisor},
+///     temperature::Millicelsius,
+///     SlaveAddress, MCP9808,
+/// };
+///
+/// let mut mcp9808 = MCP9808::new(Simulator::new(SlaveAddress::Default), SlaveAddress::Default);
+/// let mut supervisor = Supervisor::new(DesiredState {
+///     critical_temperature: Millicelsius(80_000),
+///     resolution: Resolution::Deg0_25C,
+///     ..DesiredState::default()
+/// });
+///
+/// assert_eq!(Ok(Status::ResetDetected), supervisor.check(&mut mcp9808));
+/// assert_eq!(Ok(Status::InSync), supervisor.check(&mut mcp9808));
+/// assert_eq!(1, supervisor.reset_count());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Supervisor {
+    desired: DesiredState,
+    reset_count: u32,
+    mismatch_count: u32,
+}
+
+impl Supervisor {
+    /// Creates new supervisor maintaining `DesiredState`
+    pub fn new(desired: DesiredState) -> Self {
+        Supervisor {
+            desired,
+            reset_count: 0,
+            mismatch_count: 0,
+        }
+    }
+
+    /// Desired device state
+    pub fn desired(&self) -> DesiredState {
+        self.desired
+    }
+
+    /// Change desired device state, applied on the next `apply` or `check`
+    pub fn set_desired(&mut self, desired: DesiredState) {
+        self.desired = desired;
+    }
+
+    /// Number of detected resets to power-on defaults
+    pub fn reset_count(&self) -> u32 {
+        self.reset_count
+    }
+
+    /// Number of detected differences other than resets
+    pub fn mismatch_count(&self) -> u32 {
+        self.mismatch_count
+    }
+
+    /// Write desired state to `MCP9808`.
+    /// `Configuration` is written last, so lock bits do not block writing the limits.
+    pub fn apply<I2C, Err>(&self, mcp9808: &mut MCP9808<I2C>) -> Result<(), verify::Error<Err>>
+    where
+        I2C: i2c::Write<Error = Err> + i2c::WriteRead<Error = Err>,
+    {
+        let desired = self.desired;
+        mcp9808.write_resolution(desired.resolution)?;
+        mcp9808.write_upper_temperature(desired.upper_temperature)?;
+        mcp9808.write_lower_temperature(desired.lower_temperature)?;
+        mcp9808.write_critical_temperature(desired.critical_temperature)?;
+        mcp9808.write_configuration(desired.configuration)
+    }
+
+    /// Read device state from `MCP9808`, bypassing its register cache,
+    /// compare it with the desired state and re-apply the desired state when they differ
+    pub fn check<I2C, Err>(
+        &mut self,
+        mcp9808: &mut MCP9808<I2C>,
+    ) -> Result<Status, verify::Error<Err>>
+    where
+        I2C: i2c::WriteRead<Error = Err> + i2c::Write<Error = Err>,
+    {
+        mcp9808.invalidate_cache();
+        let actual = read_state(mcp9808)
+            .map_err(verify::Error::I2c)?
+            .comparable();
+        let desired = self.desired.comparable();
+
+        let status = if actual == desired {
+            Status::InSync
+        } else if actual == DesiredState::default().comparable() {
+            self.reset_count = self.reset_count.saturating_add(1);
+            Status::ResetDetected
+        } else {
+            self.mismatch_count = self.mismatch_count.saturating_add(1);
+            Status::Mismatch
+        };
+
+        if status != Status::InSync {
+            self.apply(mcp9808)?;
+        }
+        Ok(status)
+    }
+}
+
+/// Device state read from `MCP9808`
+fn read_state<I2C, Err>(mcp9808: &mut MCP9808<I2C>) -> Result<DesiredState, Err>
+where
+    I2C: i2c::WriteRead<Error = Err>,
+{
+    Ok(DesiredState {
+        configuration: mcp9808.read_configuration()?,
+        upper_temperature: mcp9808.read_upper_temperature()?,
+        lower_temperature: mcp9808.read_lower_temperature()?,
+        critical_temperature: mcp9808.read_critical_temperature()?,
+        resolution: mcp9808.read_resolution()?,
+    })
+}
@@ -1,17 +1,25 @@
 //! Ambient, Critical, Upper, Lower Temperature
 
-use crate::{hal::blocking::i2c, registers::Register, MCP9808};
+use crate::{hal::blocking::i2c, registers::Register, verify, MCP9808};
+use core::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, AddAssign, Neg, Sub, SubAssign},
+    str::FromStr,
+};
 use i2c_interface::generic_array::{typenum::consts::U2, GenericArray};
 
 const ALERT_CRITICAL_BIT: u8 = 1 << 7;
 const ALERT_UPPER_BIT: u8 = 1 << 6;
 const ALERT_LOWER_BIT: u8 = 1 << 5;
 const TEMPERATURE_SIGN_BIT: u8 = 1 << 4;
+const LIMIT_FRACTION_MASK: u8 = 0b1111_1100;
 
 type Raw = GenericArray<u8, U2>;
 
 /// Temperature value in Millicelsius
-#[derive(Debug, PartialOrd, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialOrd, Ord, PartialEq, Eq, Hash, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Millicelsius(pub i32);
 
 impl From<Raw> for Millicelsius {
@@ -47,8 +55,18 @@ impl Into<Raw> for Millicelsius {
     }
 }
 
+impl Millicelsius {
+    /// Temperature as stored in a limit register, which only holds 0.25°C steps
+    pub(crate) fn limit_resolution(self) -> Self {
+        let mut raw: Raw = self.into();
+        raw[1] &= LIMIT_FRACTION_MASK;
+        raw.into()
+    }
+}
+
 /// Temperature value in Celsius
 #[derive(Debug, PartialOrd, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Celsius(pub f32);
 
 impl From<Millicelsius> for Celsius {
@@ -81,8 +99,406 @@ pub trait TemperatureUnit {}
 impl TemperatureUnit for Millicelsius {}
 impl TemperatureUnit for Celsius {}
 
+#[cfg(feature = "uom")]
+impl TemperatureUnit for uom::si::f32::ThermodynamicTemperature {}
+
+#[cfg(feature = "uom")]
+impl From<Millicelsius> for uom::si::f32::ThermodynamicTemperature {
+    fn from(millicelsius: Millicelsius) -> Self {
+        use uom::si::thermodynamic_temperature::degree_celsius;
+        Self::new::<degree_celsius>(Celsius::from(millicelsius).0)
+    }
+}
+
+#[cfg(feature = "uom")]
+impl From<uom::si::f32::ThermodynamicTemperature> for Millicelsius {
+    /// Rounds to the nearest Millicelsius, saturating at the `i32` range
+    fn from(temperature: uom::si::f32::ThermodynamicTemperature) -> Self {
+        use uom::si::thermodynamic_temperature::degree_celsius;
+        // f32 kelvin carries rounding noise, truncating would lose a Millicelsius
+        Millicelsius(round_millicelsius(temperature.get::<degree_celsius>()))
+    }
+}
+
+/// Celsius `value` rounded to the nearest Millicelsius, saturating at the `i32` range
+fn round_millicelsius(value: f32) -> i32 {
+    let millicelsius = value * 1_000.0;
+    if millicelsius < 0.0 {
+        (millicelsius - 0.5) as i32
+    } else {
+        (millicelsius + 0.5) as i32
+    }
+}
+
+/// Integer division rounding half away from zero
+pub(crate) fn divide_rounded(dividend: i64, divisor: i64) -> i64 {
+    let quotient = (dividend.abs() + divisor.abs() / 2) / divisor.abs();
+    if (dividend < 0) == (divisor < 0) {
+        quotient
+    } else {
+        -quotient
+    }
+}
+
+/// Difference between two temperatures in Millicelsius
+#[derive(Debug, Default, PartialOrd, Ord, PartialEq, Eq, Hash, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TemperatureDelta(pub i32);
+
+impl TemperatureDelta {
+    /// Absolute value of the difference, saturating at `i32::MAX`
+    pub fn abs(self) -> Self {
+        TemperatureDelta(self.0.saturating_abs())
+    }
+
+    /// `self + other`, or `None` on overflow
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(TemperatureDelta)
+    }
+
+    /// `self - other`, or `None` on overflow
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(TemperatureDelta)
+    }
+
+    /// `self + other`, saturating at the `i32` range
+    pub fn saturating_add(self, other: Self) -> Self {
+        TemperatureDelta(self.0.saturating_add(other.0))
+    }
+
+    /// `self - other`, saturating at the `i32` range
+    pub fn saturating_sub(self, other: Self) -> Self {
+        TemperatureDelta(self.0.saturating_sub(other.0))
+    }
+}
+
+impl Add for TemperatureDelta {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        TemperatureDelta(self.0 + other.0)
+    }
+}
+
+impl Sub for TemperatureDelta {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        TemperatureDelta(self.0 - other.0)
+    }
+}
+
+impl Neg for TemperatureDelta {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        TemperatureDelta(-self.0)
+    }
+}
+
+impl AddAssign for TemperatureDelta {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for TemperatureDelta {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl Millicelsius {
+    /// `self - other` as a delta, or `None` on overflow
+    pub fn checked_delta(self, other: Self) -> Option<TemperatureDelta> {
+        self.0.checked_sub(other.0).map(TemperatureDelta)
+    }
+
+    /// `self - other` as a delta, saturating at the `i32` range
+    pub fn saturating_delta(self, other: Self) -> TemperatureDelta {
+        TemperatureDelta(self.0.saturating_sub(other.0))
+    }
+
+    /// Temperature raised by `delta`, or `None` on overflow
+    pub fn checked_add(self, delta: TemperatureDelta) -> Option<Self> {
+        self.0.checked_add(delta.0).map(Millicelsius)
+    }
+
+    /// Temperature lowered by `delta`, or `None` on overflow
+    pub fn checked_sub(self, delta: TemperatureDelta) -> Option<Self> {
+        self.0.checked_sub(delta.0).map(Millicelsius)
+    }
+
+    /// Temperature raised by `delta`, saturating at the `i32` range
+    pub fn saturating_add(self, delta: TemperatureDelta) -> Self {
+        Millicelsius(self.0.saturating_add(delta.0))
+    }
+
+    /// Temperature lowered by `delta`, saturating at the `i32` range
+    pub fn saturating_sub(self, delta: TemperatureDelta) -> Self {
+        Millicelsius(self.0.saturating_sub(delta.0))
+    }
+}
+
+impl Sub for Millicelsius {
+    type Output = TemperatureDelta;
+
+    fn sub(self, other: Self) -> TemperatureDelta {
+        TemperatureDelta(self.0 - other.0)
+    }
+}
+
+impl Sub<Celsius> for Millicelsius {
+    type Output = TemperatureDelta;
+
+    fn sub(self, other: Celsius) -> TemperatureDelta {
+        Celsius::from(self) - other
+    }
+}
+
+impl Add<TemperatureDelta> for Millicelsius {
+    type Output = Self;
+
+    fn add(self, delta: TemperatureDelta) -> Self {
+        Millicelsius(self.0 + delta.0)
+    }
+}
+
+impl Sub<TemperatureDelta> for Millicelsius {
+    type Output = Self;
+
+    fn sub(self, delta: TemperatureDelta) -> Self {
+        Millicelsius(self.0 - delta.0)
+    }
+}
+
+impl AddAssign<TemperatureDelta> for Millicelsius {
+    fn add_assign(&mut self, delta: TemperatureDelta) {
+        *self = *self + delta;
+    }
+}
+
+impl SubAssign<TemperatureDelta> for Millicelsius {
+    fn sub_assign(&mut self, delta: TemperatureDelta) {
+        *self = *self - delta;
+    }
+}
+
+impl Sub for Celsius {
+    type Output = TemperatureDelta;
+
+    /// Difference rounded to the nearest Millicelsius
+    fn sub(self, other: Self) -> TemperatureDelta {
+        TemperatureDelta(round_millicelsius(self.0 - other.0))
+    }
+}
+
+impl Sub<Millicelsius> for Celsius {
+    type Output = TemperatureDelta;
+
+    fn sub(self, other: Millicelsius) -> TemperatureDelta {
+        self - Celsius::from(other)
+    }
+}
+
+impl PartialEq<Celsius> for Millicelsius {
+    /// Equal when `Celsius` rounds to the same Millicelsius, as in `Sub`
+    fn eq(&self, other: &Celsius) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd<Celsius> for Millicelsius {
+    /// Compares with `Celsius` rounded to the nearest Millicelsius, as in `Sub`
+    fn partial_cmp(&self, other: &Celsius) -> Option<Ordering> {
+        if other.0.is_nan() {
+            return None;
+        }
+        Some((*self - *other).0.cmp(&0))
+    }
+}
+
+impl PartialEq<Millicelsius> for Celsius {
+    fn eq(&self, other: &Millicelsius) -> bool {
+        other == self
+    }
+}
+
+impl PartialOrd<Millicelsius> for Celsius {
+    fn partial_cmp(&self, other: &Millicelsius) -> Option<Ordering> {
+        other.partial_cmp(self).map(Ordering::reverse)
+    }
+}
+
+impl Add<TemperatureDelta> for Celsius {
+    type Output = Self;
+
+    fn add(self, delta: TemperatureDelta) -> Self {
+        Celsius(self.0 + delta.0 as f32 / 1_000.0)
+    }
+}
+
+impl Sub<TemperatureDelta> for Celsius {
+    type Output = Self;
+
+    fn sub(self, delta: TemperatureDelta) -> Self {
+        Celsius(self.0 - delta.0 as f32 / 1_000.0)
+    }
+}
+
+impl AddAssign<TemperatureDelta> for Celsius {
+    fn add_assign(&mut self, delta: TemperatureDelta) {
+        *self = *self + delta;
+    }
+}
+
+impl SubAssign<TemperatureDelta> for Celsius {
+    fn sub_assign(&mut self, delta: TemperatureDelta) {
+        *self = *self - delta;
+    }
+}
+
+impl fmt::Display for Millicelsius {
+    /// Formats as `-24063 m°C`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} m°C", self.0)
+    }
+}
+
+impl fmt::Display for TemperatureDelta {
+    /// Formats with an explicit sign as `+1250 m°C`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:+} m°C", self.0)
+    }
+}
+
+impl fmt::Display for Celsius {
+    /// Formats as `-24.063 °C`, or rounded to the given precision, e.g. `{:.1}` as `-24.1 °C`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match f.precision() {
+            Some(precision) => write!(f, "{:.*} °C", precision, self.0),
+            None => write!(f, "{} °C", self.0),
+        }
+    }
+}
+
+/// Temperature string could not be parsed
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ParseTemperatureError;
+
+/// Unit suffix of a temperature string
+#[derive(PartialEq, Clone, Copy)]
+enum Suffix {
+    Celsius,
+    Millicelsius,
+    None,
+}
+
+/// Split `-24.0625 °C`, `-24063m°C` or `-24.0625` into number and `Suffix`
+fn split_suffix(text: &str) -> (&str, Suffix) {
+    let text = text.trim();
+    for &(suffix, unit) in &[
+        ("m°C", Suffix::Millicelsius),
+        ("mC", Suffix::Millicelsius),
+        ("°C", Suffix::Celsius),
+        ("C", Suffix::Celsius),
+    ] {
+        if let Some(number) = text.strip_suffix(suffix) {
+            return (number.trim_end(), unit);
+        }
+    }
+    (text, Suffix::None)
+}
+
+/// Decimal `number` multiplied by 10^`scale`, rounded half away from zero
+fn parse_scaled(number: &str, scale: u32) -> Result<i32, ParseTemperatureError> {
+    let (negative, digits) = match number.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, number.strip_prefix('+').unwrap_or(number)),
+    };
+    let mut parts = digits.splitn(2, '.');
+    let whole = parts.next().unwrap_or_default();
+    let fraction = parts.next().unwrap_or_default();
+    let is_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+    if whole.is_empty() || !is_digits(whole) || !is_digits(fraction) {
+        return Err(ParseTemperatureError);
+    }
+
+    let mut value: i64 = 0;
+    for byte in whole.bytes() {
+        value = value * 10 + i64::from(byte - b'0');
+        if value > i64::from(i32::MAX) {
+            return Err(ParseTemperatureError);
+        }
+    }
+    let mut fraction = fraction.bytes().map(|byte| i64::from(byte - b'0'));
+    for _ in 0..scale {
+        value = value * 10 + fraction.next().unwrap_or(0);
+    }
+    if fraction.next().unwrap_or(0) >= 5 {
+        value += 1;
+    }
+
+    let value = if negative { -value } else { value };
+    if value < i64::from(i32::MIN) || value > i64::from(i32::MAX) {
+        return Err(ParseTemperatureError);
+    }
+    Ok(value as i32)
+}
+
+impl FromStr for Millicelsius {
+    type Err = ParseTemperatureError;
+
+    /// Parses `-24063 m°C`, `-24063 mC`, `-24063`, `-24.0625 °C` or `-24.0625 C`,
+    /// rounding to whole Millicelsius
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (number, suffix) = split_suffix(text);
+        let scale = if suffix == Suffix::Celsius { 3 } else { 0 };
+        parse_scaled(number, scale).map(Millicelsius)
+    }
+}
+
+impl FromStr for Celsius {
+    type Err = ParseTemperatureError;
+
+    /// Parses `-24.0625 °C`, `-24.0625 C`, `-24.0625`, `-24063 m°C` or `-24063 mC`
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (number, suffix) = split_suffix(text);
+        if number.is_empty() || !number.bytes().all(|byte| b"+-.0123456789".contains(&byte)) {
+            return Err(ParseTemperatureError);
+        }
+        let value: f32 = number.parse().map_err(|_| ParseTemperatureError)?;
+        Ok(match suffix {
+            Suffix::Millicelsius => Celsius(value / 1_000.0),
+            Suffix::Celsius | Suffix::None => Celsius(value),
+        })
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Millicelsius {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "{=i32} m°C", self.0)
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for TemperatureDelta {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "{=i32} m°C", self.0)
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Celsius {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "{=f32} °C", self.0)
+    }
+}
+
 /// Ambient temperature measurement with additional information
 #[derive(Debug, PartialOrd, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TemperatureMeasurement<Unit>
 where
     Unit: TemperatureUnit,
@@ -100,14 +516,31 @@ where
     pub is_lower: bool,
 }
 
+#[cfg(feature = "defmt")]
+impl<Unit> defmt::Format for TemperatureMeasurement<Unit>
+where
+    Unit: TemperatureUnit + defmt::Format,
+{
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "{} critical={=bool} upper={=bool} lower={=bool}",
+            self.temperature,
+            self.is_critical,
+            self.is_upper,
+            self.is_lower
+        )
+    }
+}
+
 impl<Unit> From<Raw> for TemperatureMeasurement<Unit>
 where
-    Unit: From<Raw> + TemperatureUnit,
+    Unit: From<Millicelsius> + TemperatureUnit,
 {
     fn from(raw: Raw) -> Self {
         let msb = raw[0];
         TemperatureMeasurement {
-            temperature: raw.into(),
+            temperature: Millicelsius::from(raw).into(),
             is_critical: !msb & ALERT_CRITICAL_BIT == 0,
             is_upper: !msb & ALERT_UPPER_BIT == 0,
             is_lower: !msb & ALERT_LOWER_BIT == 0,
@@ -122,10 +555,9 @@ impl<I2C> MCP9808<I2C> {
     ) -> Result<TemperatureMeasurement<Unit>, Err>
     where
         I2C: i2c::WriteRead<Error = Err>,
-        Unit: From<Raw> + TemperatureUnit,
+        Unit: From<Millicelsius> + TemperatureUnit,
     {
-        self.i2c_interface
-            .read_register(Register::AmbientTemperatureRegister)
+        self.read_register(Register::AmbientTemperatureRegister)
             .map(TemperatureMeasurement::from)
     }
 
@@ -133,62 +565,68 @@ impl<I2C> MCP9808<I2C> {
     pub fn read_upper_temperature<Unit, Err>(&mut self) -> Result<Unit, Err>
     where
         I2C: i2c::WriteRead<Error = Err>,
-        Unit: From<Raw> + TemperatureUnit,
+        Unit: From<Millicelsius> + TemperatureUnit,
     {
-        self.i2c_interface
-            .read_register(Register::UpperTemperatureRegister)
-            .map(Unit::from)
+        self.read_register(Register::UpperTemperatureRegister)
+            .map(|raw| Millicelsius::from(raw).into())
     }
 
     /// Write Temperature `Unit` to `UpperTemperatureRegister`
-    pub fn write_upper_temperature<Unit, Err>(&mut self, temperature: Unit) -> Result<(), Err>
+    pub fn write_upper_temperature<Unit, Err>(
+        &mut self,
+        temperature: Unit,
+    ) -> Result<(), verify::Error<Err>>
     where
-        I2C: i2c::Write<Error = Err>,
-        Unit: Into<Raw> + TemperatureUnit,
+        I2C: i2c::Write<Error = Err> + i2c::WriteRead<Error = Err>,
+        Unit: Into<Millicelsius> + TemperatureUnit,
     {
-        self.i2c_interface
-            .write_register(Register::UpperTemperatureRegister, temperature.into())
+        let temperature: Millicelsius = temperature.into();
+        self.write_register(Register::UpperTemperatureRegister, temperature.into())
     }
 
     /// Read Temperature `Unit` from `LowerTemperatureRegister`
     pub fn read_lower_temperature<Unit, Err>(&mut self) -> Result<Unit, Err>
     where
         I2C: i2c::WriteRead<Error = Err>,
-        Unit: From<Raw> + TemperatureUnit,
+        Unit: From<Millicelsius> + TemperatureUnit,
     {
-        self.i2c_interface
-            .read_register(Register::LowerTemperatureRegister)
-            .map(Unit::from)
+        self.read_register(Register::LowerTemperatureRegister)
+            .map(|raw| Millicelsius::from(raw).into())
     }
 
     /// Write Temperature `Unit` to `LowerTemperatureRegister`
-    pub fn write_lower_temperature<Unit, Err>(&mut self, temperature: Unit) -> Result<(), Err>
+    pub fn write_lower_temperature<Unit, Err>(
+        &mut self,
+        temperature: Unit,
+    ) -> Result<(), verify::Error<Err>>
     where
-        I2C: i2c::Write<Error = Err>,
-        Unit: Into<Raw> + TemperatureUnit,
+        I2C: i2c::Write<Error = Err> + i2c::WriteRead<Error = Err>,
+        Unit: Into<Millicelsius> + TemperatureUnit,
     {
-        self.i2c_interface
-            .write_register(Register::LowerTemperatureRegister, temperature.into())
+        let temperature: Millicelsius = temperature.into();
+        self.write_register(Register::LowerTemperatureRegister, temperature.into())
     }
 
     /// Read Temperature `Unit` from `CriticalTemperatureRegister`
     pub fn read_critical_temperature<Unit, Err>(&mut self) -> Result<Unit, Err>
     where
         I2C: i2c::WriteRead<Error = Err>,
-        Unit: From<Raw> + TemperatureUnit,
+        Unit: From<Millicelsius> + TemperatureUnit,
     {
-        self.i2c_interface
-            .read_register(Register::CriticalTemperatureRegister)
-            .map(Unit::from)
+        self.read_register(Register::CriticalTemperatureRegister)
+            .map(|raw| Millicelsius::from(raw).into())
     }
 
     /// Write Temperature `Unit` to `CriticalTemperatureRegister`
-    pub fn write_critical_temperature<Unit, Err>(&mut self, temperature: Unit) -> Result<(), Err>
+    pub fn write_critical_temperature<Unit, Err>(
+        &mut self,
+        temperature: Unit,
+    ) -> Result<(), verify::Error<Err>>
     where
-        I2C: i2c::Write<Error = Err>,
-        Unit: Into<Raw> + TemperatureUnit,
+        I2C: i2c::Write<Error = Err> + i2c::WriteRead<Error = Err>,
+        Unit: Into<Millicelsius> + TemperatureUnit,
     {
-        self.i2c_interface
-            .write_register(Register::CriticalTemperatureRegister, temperature.into())
+        let temperature: Millicelsius = temperature.into();
+        self.write_register(Register::CriticalTemperatureRegister, temperature.into())
     }
 }
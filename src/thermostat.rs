@@ -0,0 +1,211 @@
+//! Bang-bang Thermostat
+
+use crate::{
+    clock::Clock,
+    hal::{blocking::i2c, digital::v2::OutputPin},
+    temperature::{Millicelsius, TemperatureMeasurement},
+    MCP9808,
+};
+
+/// Direction in which the controlled output moves the temperature
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Polarity {
+    /// Output drives a heater, it is switched on below the setpoint
+    Heating,
+    /// Output drives a cooler or fan, it is switched on above the setpoint
+    Cooling,
+}
+
+/// Thermostat output state
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Output {
+    /// Output pin is driven low
+    Off,
+    /// Output pin is driven high
+    On,
+}
+
+/// Thermostat state after a control step
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ThermostatState {
+    /// Temperature the control step was run with
+    pub temperature: Millicelsius,
+
+    /// Output state after the control step
+    pub output: Output,
+
+    /// Output should switch, but is held by minimum on or off time
+    pub held: bool,
+}
+
+/// Thermostat error
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Error<I2cError, PinError> {
+    /// Reading ambient temperature failed
+    I2c(I2cError),
+    /// Driving output pin failed
+    Pin(PinError),
+}
+
+/// Bang-bang thermostat driving an `OutputPin`
+///
+/// The hysteresis band is centered on the setpoint. With `Polarity::Heating` the output
+/// is switched on below `setpoint - hysteresis / 2` and off at or above
+/// `setpoint + hysteresis / 2`, `Polarity::Cooling` works the other way around.
+/// Within the band the output keeps its previous state.
+pub struct Thermostat<Pin, Clk> {
+    pin: Pin,
+    clock: Clk,
+    setpoint: Millicelsius,
+    hysteresis: Millicelsius,
+    polarity: Polarity,
+    min_on_time_ms: u64,
+    min_off_time_ms: u64,
+    output: Output,
+    last_switch_ms: Option<u64>,
+    state: Option<ThermostatState>,
+}
+
+impl<Pin, Clk> Thermostat<Pin, Clk>
+where
+    Pin: OutputPin,
+    Clk: Clock,
+{
+    /// Creates new thermostat with `setpoint`, `hysteresis` band width and `Polarity`.
+    /// Output is assumed off and is driven on the first control step.
+    pub fn new<Unit>(
+        pin: Pin,
+        clock: Clk,
+        setpoint: Unit,
+        hysteresis: Unit,
+        polarity: Polarity,
+    ) -> Self
+    where
+        Unit: Into<Millicelsius>,
+    {
+        Thermostat {
+            pin,
+            clock,
+            setpoint: setpoint.into(),
+            hysteresis: hysteresis.into(),
+            polarity,
+            min_on_time_ms: 0,
+            min_off_time_ms: 0,
+            output: Output::Off,
+            last_switch_ms: None,
+            state: None,
+        }
+    }
+
+    /// Change setpoint
+    pub fn set_setpoint<Unit>(&mut self, setpoint: Unit)
+    where
+        Unit: Into<Millicelsius>,
+    {
+        self.setpoint = setpoint.into();
+    }
+
+    /// Change hysteresis band width
+    pub fn set_hysteresis<Unit>(&mut self, hysteresis: Unit)
+    where
+        Unit: Into<Millicelsius>,
+    {
+        self.hysteresis = hysteresis.into();
+    }
+
+    /// Minimum time in milliseconds the output stays on before it can be switched off
+    pub fn set_min_on_time(&mut self, milliseconds: u64) {
+        self.min_on_time_ms = milliseconds;
+    }
+
+    /// Minimum time in milliseconds the output stays off before it can be switched on
+    pub fn set_min_off_time(&mut self, milliseconds: u64) {
+        self.min_off_time_ms = milliseconds;
+    }
+
+    /// Current setpoint
+    pub fn setpoint(&self) -> Millicelsius {
+        self.setpoint
+    }
+
+    /// Current output state
+    pub fn output(&self) -> Output {
+        self.output
+    }
+
+    /// State after the last control step, `None` before the first step
+    pub fn state(&self) -> Option<ThermostatState> {
+        self.state
+    }
+
+    /// Run a control step with ambient temperature read from `MCP9808`
+    pub fn step<I2C, Err>(
+        &mut self,
+        mcp9808: &mut MCP9808<I2C>,
+    ) -> Result<ThermostatState, Error<Err, Pin::Error>>
+    where
+        I2C: i2c::WriteRead<Error = Err>,
+    {
+        let measurement: TemperatureMeasurement<Millicelsius> =
+            mcp9808.read_ambient_temperature().map_err(Error::I2c)?;
+        self.update(measurement.temperature).map_err(Error::Pin)
+    }
+
+    /// Run a control step with already measured `temperature`
+    pub fn update<Unit>(&mut self, temperature: Unit) -> Result<ThermostatState, Pin::Error>
+    where
+        Unit: Into<Millicelsius>,
+    {
+        let temperature = temperature.into();
+        let target = self.demand(temperature).unwrap_or(self.output);
+        let now = self.clock.now_ms();
+
+        let mut held = false;
+        if target != self.output || self.last_switch_ms.is_none() {
+            let min_time = match self.output {
+                Output::On => self.min_on_time_ms,
+                Output::Off => self.min_off_time_ms,
+            };
+            let can_switch = match self.last_switch_ms {
+                Some(last) => now.saturating_sub(last) >= min_time,
+                None => true,
+            };
+
+            if can_switch {
+                match target {
+                    Output::On => self.pin.set_high()?,
+                    Output::Off => self.pin.set_low()?,
+                }
+                self.output = target;
+                self.last_switch_ms = Some(now);
+            } else {
+                held = true;
+            }
+        }
+
+        let state = ThermostatState {
+            temperature,
+            output: self.output,
+            held,
+        };
+        self.state = Some(state);
+        Ok(state)
+    }
+
+    /// Release the output pin and clock
+    pub fn release(self) -> (Pin, Clk) {
+        (self.pin, self.clock)
+    }
+
+    fn demand(&self, temperature: Millicelsius) -> Option<Output> {
+        let half_band = self.hysteresis.0 / 2;
+        let (lower, upper) = (self.setpoint.0 - half_band, self.setpoint.0 + half_band);
+        match self.polarity {
+            Polarity::Heating if temperature.0 < lower => Some(Output::On),
+            Polarity::Heating if temperature.0 >= upper => Some(Output::Off),
+            Polarity::Cooling if temperature.0 > upper => Some(Output::On),
+            Polarity::Cooling if temperature.0 <= lower => Some(Output::Off),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,356 @@
+//! I2C Transaction Tracing
+
+use crate::{
+    configuration::Configuration,
+    device_id_revision::{DeviceId, DeviceRevision},
+    hal::blocking::i2c,
+    manufacturer_id::ManufacturerId,
+    registers::Register,
+    resolution::Resolution,
+    temperature::{Millicelsius, TemperatureMeasurement},
+};
+use core::fmt;
+use i2c_interface::generic_array::{
+    typenum::consts::{U1, U2},
+    ArrayLength, GenericArray,
+};
+
+/// Most data bytes kept per transaction, the size of the largest register
+pub const MAX_DATA_LEN: usize = 2;
+
+type Raw = GenericArray<u8, U2>;
+
+/// Direction of the data phase
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Operation {
+    /// Register pointer and data written to the device
+    Write,
+    /// Register pointer written, data read from the device
+    Read,
+}
+
+/// Recorded I2C transaction
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Transaction {
+    /// Direction of the data phase
+    pub operation: Operation,
+
+    /// Device address
+    pub address: u8,
+
+    /// Register pointer, first byte written
+    pub pointer: Option<u8>,
+
+    /// Data written after the pointer or data read, see `data()`
+    pub data: [u8; MAX_DATA_LEN],
+
+    /// Number of valid bytes in `data`
+    pub len: usize,
+
+    /// Transaction succeeded
+    pub ok: bool,
+}
+
+impl Transaction {
+    /// Data written after the pointer or data read
+    pub fn data(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+
+    /// Datasheet name of the register the pointer selects
+    pub fn register_name(&self) -> Option<&'static str> {
+        self.register().map(Register::name)
+    }
+
+    /// Register value carried by a successful transaction
+    pub fn decode(&self) -> Option<RegisterValue> {
+        let register = self.register()?;
+        if !self.ok {
+            return None;
+        }
+        if register == Register::ResolutionRegister {
+            return match self.data() {
+                [value] => Some(RegisterValue::Resolution(
+                    GenericArray::<u8, U1>::from([*value]).into(),
+                )),
+                _ => None,
+            };
+        }
+        if self.len != 2 {
+            return None;
+        }
+
+        let raw = Raw::clone_from_slice(self.data());
+        Some(match register {
+            Register::ConfigurationRegister => RegisterValue::Configuration(raw.into()),
+            Register::UpperTemperatureRegister => RegisterValue::UpperTemperature(raw.into()),
+            Register::LowerTemperatureRegister => RegisterValue::LowerTemperature(raw.into()),
+            Register::CriticalTemperatureRegister => RegisterValue::CriticalTemperature(raw.into()),
+            Register::AmbientTemperatureRegister => RegisterValue::AmbientTemperature(raw.into()),
+            Register::ManufacturerIdRegister => RegisterValue::ManufacturerId(raw.into()),
+            Register::DeviceIdRevisionRegister => {
+                RegisterValue::DeviceInformation(DeviceId(raw[0]), DeviceRevision(raw[1]))
+            }
+            Register::ResolutionRegister => return None,
+        })
+    }
+
+    pub(crate) fn new(
+        operation: Operation,
+        address: u8,
+        pointer: Option<u8>,
+        data: &[u8],
+        ok: bool,
+    ) -> Self {
+        let len = data.len().min(MAX_DATA_LEN);
+        let mut bytes = [0; MAX_DATA_LEN];
+        bytes[..len].copy_from_slice(&data[..len]);
+        Transaction {
+            operation,
+            address,
+            pointer,
+            data: bytes,
+            len,
+            ok,
+        }
+    }
+
+    fn register(&self) -> Option<Register> {
+        self.pointer.and_then(Register::from_pointer)
+    }
+}
+
+impl fmt::Display for Transaction {
+    /// Formats as `read 0x18 T_A [c1 90] ok AmbientTemperature(..)`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let operation = match self.operation {
+            Operation::Write => "write",
+            Operation::Read => "read",
+        };
+        write!(f, "{} {:#04x}", operation, self.address)?;
+        match (self.register_name(), self.pointer) {
+            (Some(name), _) => write!(f, " {}", name)?,
+            (None, Some(pointer)) => write!(f, " {:#04x}", pointer)?,
+            (None, None) => {}
+        }
+        write!(f, " [")?;
+        for (index, byte) in self.data().iter().enumerate() {
+            if index > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{:02x}", byte)?;
+        }
+        write!(f, "] {}", if self.ok { "ok" } else { "error" })?;
+        if let Some(value) = self.decode() {
+            write!(f, " {:?}", value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Decoded MCP9808 register value
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum RegisterValue {
+    /// CONFIG register
+    Configuration(Configuration),
+    /// T_UPPER register
+    UpperTemperature(Millicelsius),
+    /// T_LOWER register
+    LowerTemperature(Millicelsius),
+    /// T_CRIT register
+    CriticalTemperature(Millicelsius),
+    /// T_A register
+    AmbientTemperature(TemperatureMeasurement<Millicelsius>),
+    /// Manufacturer ID register
+    ManufacturerId(ManufacturerId),
+    /// Device ID and Revision register
+    DeviceInformation(DeviceId, DeviceRevision),
+    /// Resolution register
+    Resolution(Resolution),
+}
+
+/// Destination of recorded transactions
+pub trait TraceSink {
+    /// Record a transaction
+    fn record(&mut self, transaction: Transaction);
+}
+
+impl<F> TraceSink for F
+where
+    F: FnMut(Transaction),
+{
+    fn record(&mut self, transaction: Transaction) {
+        self(transaction)
+    }
+}
+
+/// Ring buffer keeping the last `N` transactions
+#[derive(Debug, Clone, Default)]
+pub struct TraceBuffer<N>
+where
+    N: ArrayLength<Option<Transaction>>,
+{
+    transactions: GenericArray<Option<Transaction>, N>,
+    next: usize,
+    dropped: u32,
+}
+
+impl<N> TraceBuffer<N>
+where
+    N: ArrayLength<Option<Transaction>>,
+{
+    /// Creates new empty buffer
+    pub fn new() -> Self {
+        TraceBuffer {
+            transactions: GenericArray::default(),
+            next: 0,
+            dropped: 0,
+        }
+    }
+
+    /// Recorded transactions, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &Transaction> {
+        let (newer, older) = self.transactions.split_at(self.next);
+        older.iter().chain(newer).filter_map(Option::as_ref)
+    }
+
+    /// Number of recorded transactions
+    pub fn len(&self) -> usize {
+        self.transactions
+            .iter()
+            .filter(|slot| slot.is_some())
+            .count()
+    }
+
+    /// No transaction was recorded
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of transactions overwritten by newer ones
+    pub fn dropped(&self) -> u32 {
+        self.dropped
+    }
+
+    /// Forget all transactions
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl<N> TraceSink for TraceBuffer<N>
+where
+    N: ArrayLength<Option<Transaction>>,
+{
+    fn record(&mut self, transaction: Transaction) {
+        if self.transactions[self.next].is_some() {
+            self.dropped = self.dropped.saturating_add(1);
+        }
+        self.transactions[self.next] = Some(transaction);
+        self.next = (self.next + 1) % self.transactions.len();
+    }
+}
+
+/// I2C bus recording every transaction into a `TraceSink`
+///
+/// # Example
+///
+/// ```
+/// use i2c_interface::generic_array::typenum::consts::U8;
+/// use mcp9808::{
+///     resolution::Resolution,
+///     simulator::Simulator,
+///     trace::{RegisterValue, TraceBuffer, TracingI2c},
+///     SlaveAddress, MCP9808,
+/// };
+///
+/// let bus = TracingI2c::new(Simulator::new(SlaveAddress::Default), TraceBuffer::<U8>::new());
+/// let mut mcp9808 = MCP9808::new(bus, SlaveAddress::Default);
+/// mcp9808.write_resolution(Resolution::Deg0_25C).unwrap();
+///
+/// let transaction = mcp9808.i2c().sink().iter().next().unwrap();
+/// assert_eq!(Some("RESOLUTION"), transaction.register_name());
+/// assert_eq!(
+///     Some(RegisterValue::Resolution(Resolution::Deg0_25C)),
+///     transaction.decode()
+/// );
+/// ```
+pub struct TracingI2c<I2C, S> {
+    i2c: I2C,
+    sink: S,
+}
+
+impl<I2C, S> TracingI2c<I2C, S>
+where
+    S: TraceSink,
+{
+    /// Creates new bus recording transactions of `i2c` into `sink`
+    pub fn new(i2c: I2C, sink: S) -> Self {
+        TracingI2c { i2c, sink }
+    }
+
+    /// Shared reference to the `TraceSink`
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Mutable reference to the `TraceSink`
+    pub fn sink_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    /// Release the `I2C` and the `TraceSink`
+    pub fn release(self) -> (I2C, S) {
+        (self.i2c, self.sink)
+    }
+}
+
+impl<I2C, S, Err> i2c::Write for TracingI2c<I2C, S>
+where
+    I2C: i2c::Write<Error = Err>,
+    S: TraceSink,
+{
+    type Error = Err;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        let result = self.i2c.write(address, bytes);
+        let (pointer, data) = match bytes.split_first() {
+            Some((pointer, data)) => (Some(*pointer), data),
+            None => (None, bytes),
+        };
+        self.sink.record(Transaction::new(
+            Operation::Write,
+            address,
+            pointer,
+            data,
+            result.is_ok(),
+        ));
+        result
+    }
+}
+
+impl<I2C, S, Err> i2c::WriteRead for TracingI2c<I2C, S>
+where
+    I2C: i2c::WriteRead<Error = Err>,
+    S: TraceSink,
+{
+    type Error = Err;
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let result = self.i2c.write_read(address, bytes, buffer);
+        let data: &[u8] = if result.is_ok() { buffer } else { &[] };
+        self.sink.record(Transaction::new(
+            Operation::Read,
+            address,
+            bytes.first().cloned(),
+            data,
+            result.is_ok(),
+        ));
+        result
+    }
+}
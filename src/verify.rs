@@ -0,0 +1,125 @@
+//! Verified Register Writes
+//!
+//! The device silently ignores writes blocked by the lock bits,
+//! so with write verification enabled every register write is followed by a readback
+//! compared with the written value. The self-clearing Interrupt Clear bit, the read-only
+//! Alert Output Status bit and bits a register cannot hold are masked before comparing.
+//!
+//! Write methods return `verify::Error`, wrapping the bus error, whether or not
+//! verification is enabled.
+
+use crate::{hal::blocking::i2c, registers::Register, MCP9808};
+use i2c_interface::generic_array::{ArrayLength, GenericArray};
+
+/// CONFIG bits compared, without unused, Interrupt Clear and Alert Output Status bits
+const CONFIGURATION_MASK: u16 = 0b0000_0111_1100_1111;
+/// Limit register bits compared, sign, integer and 0.25°C fraction bits
+const LIMIT_MASK: u16 = 0b0001_1111_1111_1100;
+/// Resolution register bits compared
+const RESOLUTION_MASK: u16 = 0b0000_0011;
+
+/// Register write error
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Error<I2cError> {
+    /// I2C bus error
+    I2c(I2cError),
+    /// Value read back differs from the written value
+    VerifyFailed {
+        /// Written register
+        register: Register,
+        /// Written value, as the register can hold it
+        expected: u16,
+        /// Value read back
+        actual: u16,
+    },
+}
+
+impl<I2C> MCP9808<I2C> {
+    /// Read back and compare every register write, reporting ignored writes as `VerifyFailed`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcp9808::{
+    ///     configuration::{Configuration, CriticalTemperatureLock},
+    ///     simulator::Simulator,
+    ///     temperature::Millicelsius,
+    ///     verify::Error,
+    ///     Register, SlaveAddress, MCP9808,
+    /// };
+    ///
+    /// let mut mcp9808 = MCP9808::new(Simulator::new(SlaveAddress::Default), SlaveAddress::Default);
+    /// mcp9808.enable_write_verification();
+    /// mcp9808
+    ///     .write_configuration(Configuration {
+    ///         critical_temperature_lock: CriticalTemperatureLock::Locked,
+    ///         ..Configuration::default()
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     Err(Error::VerifyFailed {
+    ///         register: Register::CriticalTemperatureRegister,
+    ///         expected: 0x0500,
+    ///         actual: 0x0000,
+    ///     }),
+    ///     mcp9808.write_critical_temperature(Millicelsius(80_000))
+    /// );
+    /// ```
+    pub fn enable_write_verification(&mut self) {
+        self.verify = true;
+    }
+
+    /// Write registers without reading them back
+    pub fn disable_write_verification(&mut self) {
+        self.verify = false;
+    }
+
+    /// Write verification is enabled
+    pub fn is_write_verification_enabled(&self) -> bool {
+        self.verify
+    }
+
+    /// Read back register from the bus and compare it with the written value
+    pub(crate) fn verify_register<N, Err>(
+        &mut self,
+        register: Register,
+        written: &GenericArray<u8, N>,
+    ) -> Result<GenericArray<u8, N>, Error<Err>>
+    where
+        I2C: i2c::WriteRead<Error = Err>,
+        N: ArrayLength<u8>,
+    {
+        let raw: GenericArray<u8, N> = self
+            .i2c_interface
+            .read_register(register)
+            .map_err(Error::I2c)?;
+        let mask = register_mask(register);
+        let expected = value(written) & mask;
+        let actual = value(&raw) & mask;
+        if expected == actual {
+            Ok(raw)
+        } else {
+            Err(Error::VerifyFailed {
+                register,
+                expected,
+                actual,
+            })
+        }
+    }
+}
+
+/// Bits of a writable register kept by the device
+pub(crate) fn register_mask(register: Register) -> u16 {
+    match register {
+        Register::ConfigurationRegister => CONFIGURATION_MASK,
+        Register::ResolutionRegister => RESOLUTION_MASK,
+        _ => LIMIT_MASK,
+    }
+}
+
+/// Register bytes as big-endian value
+fn value(raw: &[u8]) -> u16 {
+    raw.iter()
+        .fold(0, |value, &byte| value << 8 | u16::from(byte))
+}
@@ -81,4 +81,46 @@ mod address {
             );
         }
     }
+
+    #[cfg(test)]
+    mod conversion {
+        use mcp9808::{InvalidAddressError, SlaveAddress};
+        use std::convert::TryFrom;
+
+        #[test]
+        fn from_u8() {
+            assert_eq!(Ok(SlaveAddress::Default), SlaveAddress::try_from(0x18));
+            assert_eq!(
+                Ok(SlaveAddress::Alternative {
+                    a2: true,
+                    a1: false,
+                    a0: true
+                }),
+                SlaveAddress::try_from(0x1d)
+            );
+            for &address in &[0x00, 0x17, 0x20, 0x98, 0xff] {
+                assert_eq!(Err(InvalidAddressError), SlaveAddress::try_from(address));
+            }
+        }
+
+        #[test]
+        fn from_str() {
+            assert_eq!(Ok(SlaveAddress::Default), "0x18".parse());
+            assert_eq!(Ok(SlaveAddress::try_from(0x1f).unwrap()), "1f".parse());
+            for text in &["", "0x", "0x20", "24", "0x1g", "0x018 "] {
+                assert_eq!(
+                    Err(InvalidAddressError),
+                    text.parse::<SlaveAddress>(),
+                    "{}",
+                    text
+                );
+            }
+        }
+
+        #[test]
+        fn all() {
+            let addresses: Vec<u8> = SlaveAddress::all().map(u8::from).collect();
+            assert_eq!((0x18..=0x1f).collect::<Vec<u8>>(), addresses);
+        }
+    }
 }
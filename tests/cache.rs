@@ -0,0 +1,211 @@
+#[cfg(test)]
+mod cache {
+    use embedded_hal::blocking::i2c;
+    use mcp9808::{
+        cache::CacheStats,
+        configuration::AlertOutputStatus,
+        health::HealthMonitor,
+        resolution::Resolution,
+        simulator::{Error, Simulator},
+        supervisor::{DesiredState, Status, Supervisor},
+        temperature::{Millicelsius, TemperatureMeasurement},
+        SlaveAddress, MCP9808,
+    };
+
+    struct CountingBus {
+        simulator: Simulator,
+        reads: u32,
+        /// Report Alert Output Status asserted in CONFIG reads
+        alert_asserted: bool,
+        /// Report a foreign Manufacturer ID, as after an ESD event
+        identity_changed: bool,
+    }
+
+    impl i2c::Write for CountingBus {
+        type Error = Error;
+
+        fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+            self.simulator.write(address, bytes)
+        }
+    }
+
+    impl i2c::WriteRead for CountingBus {
+        type Error = Error;
+
+        fn write_read(
+            &mut self,
+            address: u8,
+            bytes: &[u8],
+            buffer: &mut [u8],
+        ) -> Result<(), Self::Error> {
+            self.reads += 1;
+            self.simulator.write_read(address, bytes, buffer)?;
+            match bytes[0] {
+                0x01 if self.alert_asserted => buffer[1] |= 1 << 4,
+                0x06 if self.identity_changed => buffer[1] = 0,
+                _ => {}
+            }
+            Ok(())
+        }
+    }
+
+    fn mcp9808() -> MCP9808<CountingBus> {
+        let bus = CountingBus {
+            simulator: Simulator::new(SlaveAddress::Default),
+            reads: 0,
+            alert_asserted: false,
+            identity_changed: false,
+        };
+        MCP9808::new(bus, SlaveAddress::Default)
+    }
+
+    fn read_static_registers(mcp9808: &mut MCP9808<CountingBus>) {
+        mcp9808.read_configuration().unwrap();
+        mcp9808.read_upper_temperature::<Millicelsius, _>().unwrap();
+        mcp9808.read_lower_temperature::<Millicelsius, _>().unwrap();
+        mcp9808
+            .read_critical_temperature::<Millicelsius, _>()
+            .unwrap();
+        mcp9808.read_resolution().unwrap();
+        mcp9808.read_manufacturer_id().unwrap();
+        mcp9808.read_device_information().unwrap();
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let mut mcp9808 = mcp9808();
+        assert!(!mcp9808.is_cache_enabled());
+        read_static_registers(&mut mcp9808);
+        read_static_registers(&mut mcp9808);
+
+        assert_eq!(14, mcp9808.i2c().reads);
+        assert_eq!(CacheStats::default(), mcp9808.cache_stats());
+    }
+
+    #[test]
+    fn static_registers_are_cached() {
+        let mut mcp9808 = mcp9808();
+        mcp9808.enable_cache();
+        read_static_registers(&mut mcp9808);
+        read_static_registers(&mut mcp9808);
+
+        assert_eq!(8, mcp9808.i2c().reads);
+        assert_eq!(CacheStats { hits: 6, misses: 6 }, mcp9808.cache_stats());
+
+        mcp9808.reset_cache_stats();
+        assert_eq!(CacheStats::default(), mcp9808.cache_stats());
+
+        mcp9808.disable_cache();
+        read_static_registers(&mut mcp9808);
+        assert_eq!(15, mcp9808.i2c().reads);
+    }
+
+    #[test]
+    fn ambient_temperature_is_not_cached() {
+        let mut mcp9808 = mcp9808();
+        mcp9808.enable_cache();
+
+        for temperature in &[20_000, 21_000] {
+            mcp9808
+                .i2c_mut()
+                .simulator
+                .set_ambient_temperature(Millicelsius(*temperature));
+            let measurement: TemperatureMeasurement<Millicelsius> =
+                mcp9808.read_ambient_temperature().unwrap();
+            assert_eq!(Millicelsius(*temperature), measurement.temperature);
+        }
+        assert_eq!(2, mcp9808.i2c().reads);
+        assert_eq!(CacheStats::default(), mcp9808.cache_stats());
+    }
+
+    #[test]
+    fn configuration_is_not_cached() {
+        let mut mcp9808 = mcp9808();
+        mcp9808.enable_cache();
+        let configuration = mcp9808.read_configuration().unwrap();
+        assert_eq!(
+            AlertOutputStatus::NotAsserted,
+            configuration.alert_output_status
+        );
+
+        mcp9808.i2c_mut().alert_asserted = true;
+        let configuration = mcp9808.read_configuration().unwrap();
+        assert_eq!(
+            AlertOutputStatus::Asserted,
+            configuration.alert_output_status
+        );
+        assert_eq!(2, mcp9808.i2c().reads);
+        assert_eq!(CacheStats::default(), mcp9808.cache_stats());
+    }
+
+    #[test]
+    fn write_through() {
+        let mut mcp9808 = mcp9808();
+        mcp9808.enable_cache();
+
+        mcp9808.write_resolution(Resolution::Deg0_25C).unwrap();
+        mcp9808
+            .write_upper_temperature(Millicelsius(30_100))
+            .unwrap();
+        assert_eq!(Ok(Resolution::Deg0_25C), mcp9808.read_resolution());
+        assert_eq!(Ok(Millicelsius(30_000)), mcp9808.read_upper_temperature());
+        assert_eq!(0, mcp9808.i2c().reads);
+        assert_eq!(CacheStats { hits: 2, misses: 0 }, mcp9808.cache_stats());
+    }
+
+    #[test]
+    fn verified_write_caches_read_back_value() {
+        let mut mcp9808 = mcp9808();
+        mcp9808.enable_cache();
+        mcp9808.enable_write_verification();
+
+        mcp9808
+            .write_upper_temperature(Millicelsius(30_000))
+            .unwrap();
+        assert_eq!(1, mcp9808.i2c().reads);
+        assert_eq!(Ok(Millicelsius(30_000)), mcp9808.read_upper_temperature());
+        assert_eq!(1, mcp9808.i2c().reads);
+    }
+
+    #[test]
+    fn identity_check_bypasses_cache() {
+        let mut mcp9808 = mcp9808();
+        mcp9808.enable_cache();
+        let mut monitor = HealthMonitor::new(Resolution::Deg0_0625C);
+        assert!(mcp9808.read_manufacturer_id().unwrap().is_valid());
+        assert_eq!(Ok(true), monitor.check_identity(&mut mcp9808));
+
+        mcp9808.i2c_mut().identity_changed = true;
+        assert_eq!(Ok(false), monitor.check_identity(&mut mcp9808));
+        assert!(mcp9808.read_manufacturer_id().unwrap().is_valid());
+        assert_eq!(CacheStats { hits: 1, misses: 1 }, mcp9808.cache_stats());
+    }
+
+    #[test]
+    fn explicit_invalidation() {
+        let mut mcp9808 = mcp9808();
+        mcp9808.enable_cache();
+        mcp9808.write_resolution(Resolution::Deg0_5C).unwrap();
+        assert_eq!(Ok(Resolution::Deg0_5C), mcp9808.read_resolution());
+
+        mcp9808.i2c_mut().simulator.power_on_reset();
+        assert_eq!(Ok(Resolution::Deg0_5C), mcp9808.read_resolution());
+        mcp9808.invalidate_cache();
+        assert_eq!(Ok(Resolution::Deg0_0625C), mcp9808.read_resolution());
+    }
+
+    #[test]
+    fn supervisor_bypasses_cache() {
+        let mut mcp9808 = mcp9808();
+        mcp9808.enable_cache();
+        let mut supervisor = Supervisor::new(DesiredState {
+            resolution: Resolution::Deg0_125C,
+            ..DesiredState::default()
+        });
+        supervisor.apply(&mut mcp9808).unwrap();
+        assert_eq!(Ok(Status::InSync), supervisor.check(&mut mcp9808));
+
+        mcp9808.i2c_mut().simulator.power_on_reset();
+        assert_eq!(Ok(Status::ResetDetected), supervisor.check(&mut mcp9808));
+    }
+}
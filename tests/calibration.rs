@@ -0,0 +1,207 @@
+#[cfg(test)]
+mod calibration {
+    use i2c_interface::generic_array::arr;
+    use mcp9808::{
+        calibration::{
+            CalibratedSensor, Calibration, CalibrationPoint, Error, LinearCalibration,
+            TableCalibration,
+        },
+        simulator::Simulator,
+        temperature::{Celsius, Millicelsius, TemperatureMeasurement},
+        SlaveAddress, MCP9808,
+    };
+
+    #[test]
+    fn offset_only() {
+        let calibration = LinearCalibration::from_offset(Celsius(0.5));
+        assert_eq!(
+            Millicelsius(25_500),
+            calibration.correct(Millicelsius(25_000))
+        );
+        assert_eq!(
+            Millicelsius(-500),
+            calibration.correct(Millicelsius(-1_000))
+        );
+        assert_eq!(
+            Millicelsius(25_000),
+            calibration.inverse(Millicelsius(25_500))
+        );
+    }
+
+    #[test]
+    fn two_point() {
+        let calibration = LinearCalibration::from_points(
+            CalibrationPoint::new(Millicelsius(80_000), Millicelsius(81_000)),
+            CalibrationPoint::new(Millicelsius(0), Millicelsius(-200)),
+        )
+        .unwrap();
+
+        assert_eq!(1_015_000, calibration.gain());
+        assert_eq!(Millicelsius(-200), calibration.offset());
+        assert_eq!(Millicelsius(-200), calibration.correct(Millicelsius(0)));
+        assert_eq!(
+            Millicelsius(40_400),
+            calibration.correct(Millicelsius(40_000))
+        );
+        assert_eq!(
+            Millicelsius(81_000),
+            calibration.correct(Millicelsius(80_000))
+        );
+        assert_eq!(
+            Millicelsius(40_000),
+            calibration.inverse(Millicelsius(40_400))
+        );
+        assert_eq!(
+            Millicelsius(-20_000),
+            calibration.inverse(Millicelsius(-20_500))
+        );
+    }
+
+    #[test]
+    fn two_point_rounds_gain() {
+        let calibration = LinearCalibration::from_points(
+            CalibrationPoint::new(Millicelsius(0), Millicelsius(0)),
+            CalibrationPoint::new(Millicelsius(3_000), Millicelsius(2_000)),
+        )
+        .unwrap();
+
+        assert_eq!(666_667, calibration.gain());
+        assert_eq!(Millicelsius(0), calibration.offset());
+    }
+
+    #[test]
+    fn invalid_linear() {
+        assert_eq!(
+            Some(Error::InvalidGain),
+            LinearCalibration::new(Millicelsius(0), 0).err()
+        );
+        assert_eq!(
+            Some(Error::Unsorted),
+            LinearCalibration::from_points(
+                CalibrationPoint::new(Millicelsius(20_000), Millicelsius(20_000)),
+                CalibrationPoint::new(Millicelsius(20_000), Millicelsius(21_000)),
+            )
+            .err()
+        );
+        assert_eq!(
+            Some(Error::Overflow),
+            LinearCalibration::from_points(
+                CalibrationPoint::new(Millicelsius(0), Millicelsius(0)),
+                CalibrationPoint::new(Millicelsius(1), Millicelsius(10_000)),
+            )
+            .err()
+        );
+        assert_eq!(
+            Some(Error::Overflow),
+            LinearCalibration::from_points(
+                CalibrationPoint::new(Millicelsius(i32::MAX - 1), Millicelsius(0)),
+                CalibrationPoint::new(Millicelsius(i32::MAX), Millicelsius(1_000)),
+            )
+            .err()
+        );
+    }
+
+    #[test]
+    fn table() {
+        let calibration = TableCalibration::new(arr![CalibrationPoint;
+            CalibrationPoint::new(Millicelsius(0), Millicelsius(200)),
+            CalibrationPoint::new(Millicelsius(50_000), Millicelsius(50_000)),
+            CalibrationPoint::new(Millicelsius(100_000), Millicelsius(101_000))
+        ])
+        .unwrap();
+
+        assert_eq!(
+            Millicelsius(-9_760),
+            calibration.correct(Millicelsius(-10_000))
+        );
+        assert_eq!(
+            Millicelsius(-10_000),
+            calibration.inverse(Millicelsius(-9_760))
+        );
+        assert_eq!(
+            Millicelsius(25_100),
+            calibration.correct(Millicelsius(25_000))
+        );
+        assert_eq!(
+            Millicelsius(50_000),
+            calibration.correct(Millicelsius(50_000))
+        );
+        assert_eq!(
+            Millicelsius(75_500),
+            calibration.correct(Millicelsius(75_000))
+        );
+        assert_eq!(
+            Millicelsius(121_400),
+            calibration.correct(Millicelsius(120_000))
+        );
+
+        assert_eq!(
+            Millicelsius(25_000),
+            calibration.inverse(Millicelsius(25_100))
+        );
+        assert_eq!(
+            Millicelsius(75_000),
+            calibration.inverse(Millicelsius(75_500))
+        );
+        assert_eq!(
+            Millicelsius(120_000),
+            calibration.inverse(Millicelsius(121_400))
+        );
+    }
+
+    #[test]
+    fn invalid_table() {
+        assert_eq!(
+            Some(Error::NotEnoughPoints),
+            TableCalibration::new(arr![CalibrationPoint;
+                CalibrationPoint::new(Millicelsius(0), Millicelsius(0))
+            ])
+            .err()
+        );
+        assert_eq!(
+            Some(Error::Unsorted),
+            TableCalibration::new(arr![CalibrationPoint;
+                CalibrationPoint::new(Millicelsius(0), Millicelsius(1_000)),
+                CalibrationPoint::new(Millicelsius(10_000), Millicelsius(500))
+            ])
+            .err()
+        );
+    }
+
+    #[test]
+    fn calibrated_sensor() {
+        let mut simulator = Simulator::new(SlaveAddress::Default);
+        simulator.set_ambient_temperature(Celsius(30.0));
+        let mcp9808 = MCP9808::new(simulator, SlaveAddress::Default);
+        let mut sensor =
+            CalibratedSensor::new(mcp9808, LinearCalibration::from_offset(Celsius(1.0)));
+
+        let measurement: TemperatureMeasurement<Celsius> =
+            sensor.read_ambient_temperature().unwrap();
+        assert_eq!(Celsius(31.0), measurement.temperature);
+
+        sensor.write_critical_temperature(Celsius(31.0)).unwrap();
+        assert_eq!(
+            Ok(Celsius(30.0)),
+            sensor.sensor().read_critical_temperature()
+        );
+        assert_eq!(Ok(Celsius(31.0)), sensor.read_critical_temperature());
+
+        let measurement: TemperatureMeasurement<Celsius> =
+            sensor.read_ambient_temperature().unwrap();
+        assert!(measurement.is_critical);
+
+        sensor
+            .write_upper_temperature(Millicelsius(31_250))
+            .unwrap();
+        sensor
+            .write_lower_temperature(Millicelsius(-9_000))
+            .unwrap();
+        assert_eq!(Ok(Millicelsius(31_250)), sensor.read_upper_temperature());
+        assert_eq!(Ok(Millicelsius(-9_000)), sensor.read_lower_temperature());
+
+        let (mut mcp9808, _) = sensor.release();
+        assert_eq!(Ok(Millicelsius(30_250)), mcp9808.read_upper_temperature());
+        assert_eq!(Ok(Millicelsius(-10_000)), mcp9808.read_lower_temperature());
+    }
+}
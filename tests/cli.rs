@@ -0,0 +1,187 @@
+#[cfg(all(test, feature = "cli"))]
+mod cli {
+    use std::process::Command;
+
+    fn mcp9808(args: &[&str]) -> (bool, String, String) {
+        let output = Command::new(env!("CARGO_BIN_EXE_mcp9808"))
+            .args(args)
+            .output()
+            .unwrap();
+        (
+            output.status.success(),
+            String::from_utf8(output.stdout).unwrap(),
+            String::from_utf8(output.stderr).unwrap(),
+        )
+    }
+
+    fn simulated(args: &[&str]) -> String {
+        let mut all = vec!["--simulate", "--temperature", "-3.25"];
+        all.extend_from_slice(args);
+        let (success, stdout, stderr) = mcp9808(&all);
+        assert!(success, "{}", stderr);
+        stdout
+    }
+
+    #[test]
+    fn read() {
+        assert_eq!(
+            "-3.25 °C critical=false upper=false lower=true\n",
+            simulated(&["read"])
+        );
+        assert_eq!(
+            "-3250 m°C critical=false upper=false lower=true\n",
+            simulated(&["read", "--unit", "millicelsius"])
+        );
+    }
+
+    #[test]
+    fn watch() {
+        assert_eq!(
+            "-3.25 °C critical=false upper=false lower=true\n".repeat(3),
+            simulated(&["watch", "--interval", "1", "--count", "3"])
+        );
+    }
+
+    #[test]
+    fn config() {
+        let stdout = simulated(&["config", "get"]);
+        assert!(stdout.starts_with("hysteresis: Deg0C\nshutdown mode: ContinuousConversion\n"));
+
+        let stdout = simulated(&[
+            "config",
+            "set",
+            "hysteresis=1.5",
+            "alert-output=on",
+            "alert-mode=interrupt",
+        ]);
+        assert!(stdout.contains("hysteresis: Deg1_5C\n"));
+        assert!(stdout.contains("alert output control: Enabled\n"));
+        assert!(stdout.contains("alert output mode: Interrupt\n"));
+    }
+
+    #[test]
+    fn limits() {
+        assert_eq!(
+            "upper: 0 °C\nlower: 0 °C\ncritical: 0 °C\n",
+            simulated(&["limits", "get"])
+        );
+        assert_eq!(
+            "upper: 30.5 °C\nlower: -10 °C\ncritical: 0 °C\n",
+            simulated(&["limits", "set", "upper=30.5", "lower=-10"])
+        );
+    }
+
+    #[test]
+    fn resolution() {
+        assert_eq!("0.0625 °C\n", simulated(&["resolution"]));
+        assert_eq!("0.25 °C\n", simulated(&["resolution", "0.25"]));
+    }
+
+    #[test]
+    fn info_and_scan() {
+        assert_eq!(
+            "manufacturer id: 0x0054\ndevice id: 0x04\nrevision: 0x00\n",
+            simulated(&["info"])
+        );
+        assert_eq!("0x18\n", simulated(&["scan"]));
+    }
+
+    #[test]
+    fn simulated_address() {
+        assert_eq!(
+            "-3.25 °C critical=false upper=false lower=true\n",
+            simulated(&["--address", "0x19", "read"])
+        );
+        assert_eq!("0x1d\n", simulated(&["--address", "0x1d", "scan"]));
+    }
+
+    #[test]
+    fn log() {
+        let stdout = simulated(&["log", "--interval", "1", "--count", "2"]);
+        let lines: Vec<&str> = stdout.lines().collect();
+        assert_eq!(3, lines.len());
+        assert_eq!(
+            "timestamp_ms,address,temperature_c,critical,upper,lower,resolution_c",
+            lines[0]
+        );
+        assert!(lines[1].ends_with(",0x18,-3.25,false,false,true,0.0625"));
+
+        let stdout = simulated(&[
+            "log",
+            "--format",
+            "json",
+            "--unit",
+            "millicelsius",
+            "--count",
+            "1",
+        ]);
+        assert!(stdout.starts_with("{\"timestamp_ms\":"));
+        assert!(stdout.ends_with(
+            ",\"address\":\"0x18\",\"temperature_mc\":-3250,\"critical\":false,\"upper\":false,\"lower\":true,\"resolution_c\":0.0625}\n"
+        ));
+    }
+
+    #[test]
+    fn log_files_and_stats() {
+        let directory = std::env::temp_dir().join(format!("mcp9808-cli-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let output = directory.join("capture.csv");
+        simulated(&[
+            "log",
+            "--interval",
+            "1",
+            "--count",
+            "3",
+            "--rotate-size",
+            "150",
+            "--output",
+            output.to_str().unwrap(),
+        ]);
+        let first = directory.join("capture-0001.csv");
+        let second = directory.join("capture-0002.csv");
+        assert!(first.exists() && second.exists());
+
+        let stdout = simulated(&[
+            "stats",
+            "--filter",
+            "median",
+            first.to_str().unwrap(),
+            second.to_str().unwrap(),
+        ]);
+        std::fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(
+            "0x18: samples=3 min=-3.25 °C max=-3.25 °C mean=-3.25 °C stddev=0 °C\n",
+            stdout
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let (success, _, stderr) = mcp9808(&["--simulate", "config", "set", "shutdown=maybe"]);
+        assert!(!success);
+        assert_eq!("error: invalid configuration shutdown=maybe\n", stderr);
+
+        let (success, _, stderr) = mcp9808(&["--simulate", "--temperature", "99999999", "read"]);
+        assert!(!success);
+        assert_eq!("error: invalid temperature 99999999\n", stderr);
+
+        let (success, _, stderr) = mcp9808(&["--simulate", "limits", "set", "upper=2147484"]);
+        assert!(!success);
+        assert_eq!("error: invalid temperature 2147484\n", stderr);
+
+        let (success, _, stderr) = mcp9808(&["--simulate", "resolution", "0.3"]);
+        assert!(!success);
+        assert_eq!(
+            "error: invalid resolution, expected 0.5, 0.25, 0.125 or 0.0625\n",
+            stderr
+        );
+
+        let (success, _, stderr) = mcp9808(&["--address", "0x50", "read"]);
+        assert!(!success);
+        assert_eq!("error: invalid address, expected 0x18 to 0x1f\n", stderr);
+
+        let (success, _, stderr) = mcp9808(&["--device", "/nonexistent/i2c-9", "read"]);
+        assert!(!success);
+        assert!(stderr.starts_with("error: /nonexistent/i2c-9: "));
+    }
+}
@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod configuration {
+    use i2c_interface::generic_array::arr;
+    use mcp9808::configuration::*;
+
+    #[test]
+    fn power_on_default() {
+        assert_eq!(
+            Configuration::default(),
+            arr![u8; 0b0000_0000, 0b0000_0000].into()
+        );
+        assert_eq!(
+            arr![u8; 0b0000_0000, 0b0000_0000],
+            Configuration::default().into()
+        );
+    }
+
+    #[test]
+    fn raw_to_configuration() {
+        assert_eq!(
+            Configuration {
+                hysteresis: Hysteresis::Deg3_0C,
+                shutdown_mode: ShutdownMode::Shutdown,
+                critical_temperature_lock: CriticalTemperatureLock::Locked,
+                alert_output_status: AlertOutputStatus::Asserted,
+                alert_output_control: AlertOutputControl::Enabled,
+                alert_output_mode: AlertOutputMode::Interrupt,
+                ..Configuration::default()
+            },
+            arr![u8; 0b0000_0101, 0b1001_1001].into()
+        );
+        assert_eq!(
+            Configuration {
+                hysteresis: Hysteresis::Deg6_0C,
+                temperature_window_lock: TemperatureWindowLock::Locked,
+                interrupt_clear: InterruptClear::Cleared,
+                alert_output_select: AlertOutputSelect::CriticalOnly,
+                alert_output_polarity: AlertOutputPolarity::ActiveHigh,
+                ..Configuration::default()
+            },
+            arr![u8; 0b0000_0110, 0b0110_0110].into()
+        );
+    }
+
+    #[test]
+    fn configuration_to_raw() {
+        assert_eq!(
+            arr![u8; 0b0000_0011, 0b1000_1010],
+            Configuration {
+                hysteresis: Hysteresis::Deg1_5C,
+                shutdown_mode: ShutdownMode::Shutdown,
+                critical_temperature_lock: CriticalTemperatureLock::Locked,
+                alert_output_control: AlertOutputControl::Enabled,
+                alert_output_polarity: AlertOutputPolarity::ActiveHigh,
+                ..Configuration::default()
+            }
+            .into()
+        );
+        assert_eq!(
+            arr![u8; 0b0000_0110, 0b0111_0101],
+            Configuration {
+                hysteresis: Hysteresis::Deg6_0C,
+                temperature_window_lock: TemperatureWindowLock::Locked,
+                interrupt_clear: InterruptClear::Cleared,
+                alert_output_status: AlertOutputStatus::Asserted,
+                alert_output_select: AlertOutputSelect::CriticalOnly,
+                alert_output_mode: AlertOutputMode::Interrupt,
+                ..Configuration::default()
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            "hysteresis=0 shutdown=off critical-lock=off window-lock=off interrupt-clear=off \
+             alert-output=off alert-select=all alert-polarity=low alert-mode=comparator alert-status=off",
+            Configuration::default().to_string()
+        );
+        assert_eq!(
+            "hysteresis=1.5 shutdown=on critical-lock=on window-lock=off interrupt-clear=off \
+             alert-output=on alert-select=critical alert-polarity=high alert-mode=interrupt alert-status=on",
+            Configuration {
+                hysteresis: Hysteresis::Deg1_5C,
+                shutdown_mode: ShutdownMode::Shutdown,
+                critical_temperature_lock: CriticalTemperatureLock::Locked,
+                alert_output_status: AlertOutputStatus::Asserted,
+                alert_output_control: AlertOutputControl::Enabled,
+                alert_output_select: AlertOutputSelect::CriticalOnly,
+                alert_output_polarity: AlertOutputPolarity::ActiveHigh,
+                alert_output_mode: AlertOutputMode::Interrupt,
+                ..Configuration::default()
+            }
+            .to_string()
+        );
+    }
+}
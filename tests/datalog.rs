@@ -0,0 +1,210 @@
+#[cfg(all(test, feature = "std"))]
+mod datalog {
+    use mcp9808::{
+        datalog::{Error, Format, LogReader, LogWriter, Record, RotatingLog, Rotation, Unit},
+        resolution::Resolution,
+        temperature::{Millicelsius, TemperatureMeasurement},
+    };
+    use std::{fs, path::PathBuf};
+
+    fn record(timestamp_ms: u64, temperature: i32) -> Record {
+        Record {
+            timestamp_ms,
+            address: 0x19,
+            measurement: TemperatureMeasurement {
+                temperature: Millicelsius(temperature),
+                is_critical: false,
+                is_upper: true,
+                is_lower: false,
+            },
+            resolution: Resolution::Deg0_0625C,
+        }
+    }
+
+    fn write_log(format: Format, unit: Unit, records: &[Record]) -> String {
+        let mut writer = LogWriter::new(Vec::new(), format, unit);
+        for record in records {
+            writer.write(record).unwrap();
+        }
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
+
+    fn read_log(log: &str) -> Vec<Record> {
+        LogReader::new(log.as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    /// Temporary directory, removed on drop
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(test: &str) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "mcp9808-datalog-{}-{}",
+                test,
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            TempDir(path)
+        }
+
+        fn files(&self) -> Vec<String> {
+            let mut files: Vec<String> = fs::read_dir(&self.0)
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+                .collect();
+            files.sort();
+            files
+        }
+
+        fn read(&self, name: &str) -> String {
+            fs::read_to_string(self.0.join(name)).unwrap()
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn csv() {
+        let records = [record(1_000, 25_062), record(2_000, -125)];
+
+        let log = write_log(Format::Csv, Unit::Celsius, &records);
+
+        assert_eq!(
+            "timestamp_ms,address,temperature_c,critical,upper,lower,resolution_c\n\
+             1000,0x19,25.062,false,true,false,0.0625\n\
+             2000,0x19,-0.125,false,true,false,0.0625\n",
+            log
+        );
+        assert_eq!(records.to_vec(), read_log(&log));
+    }
+
+    #[test]
+    fn json_lines() {
+        let records = [record(1_000, 25_000), record(2_000, -3_250)];
+
+        let log = write_log(Format::JsonLines, Unit::Millicelsius, &records);
+
+        assert_eq!(
+            "{\"timestamp_ms\":1000,\"address\":\"0x19\",\"temperature_mc\":25000,\"critical\":false,\"upper\":true,\"lower\":false,\"resolution_c\":0.0625}\n\
+             {\"timestamp_ms\":2000,\"address\":\"0x19\",\"temperature_mc\":-3250,\"critical\":false,\"upper\":true,\"lower\":false,\"resolution_c\":0.0625}\n",
+            log
+        );
+        assert_eq!(records.to_vec(), read_log(&log));
+        assert_eq!(
+            records.to_vec(),
+            read_log(&write_log(Format::JsonLines, Unit::Celsius, &records))
+        );
+    }
+
+    #[test]
+    fn invalid_lines() {
+        let row = "1000,0x19,25,false,true,false,0.0625";
+        let header = "timestamp_ms,address,temperature_c,critical,upper,lower,resolution_c";
+
+        let errors: Vec<Result<Record, Error>> = LogReader::new(
+            format!("{}\n{}\n\n{}\n{{\"timestamp_ms\":1}}\n", row, header, row).as_bytes(),
+        )
+        .collect();
+
+        assert!(matches!(errors[0], Err(Error::Parse { line: 1 })));
+        assert_eq!(Some(&record(1_000, 25_000)), errors[1].as_ref().ok());
+        assert!(matches!(errors[2], Err(Error::Parse { line: 5 })));
+        assert_eq!(3, errors.len());
+    }
+
+    #[test]
+    fn out_of_range_temperature() {
+        let log = "timestamp_ms,address,temperature_c,critical,upper,lower,resolution_c\n\
+                   1000,0x19,2147483.999,false,true,false,0.0625\n\
+                   2000,0x19,-2147484,false,true,false,0.0625\n";
+
+        let errors: Vec<Result<Record, Error>> = LogReader::new(log.as_bytes()).collect();
+
+        assert!(matches!(errors[0], Err(Error::Parse { line: 2 })));
+        assert!(matches!(errors[1], Err(Error::Parse { line: 3 })));
+        assert_eq!(2, errors.len());
+    }
+
+    #[test]
+    fn rotate_by_size() {
+        let directory = TempDir::new("size");
+        let rotation = Rotation {
+            max_bytes: Some(140),
+            max_duration_ms: None,
+        };
+        let mut log = RotatingLog::new(
+            directory.0.join("capture.csv"),
+            Format::Csv,
+            Unit::Celsius,
+            rotation,
+        );
+
+        for index in 0..5 {
+            log.write(&record(index * 1_000, 20_000)).unwrap();
+        }
+        assert_eq!(
+            Some(directory.0.join("capture-0003.csv").as_path()),
+            log.current_path()
+        );
+        drop(log);
+
+        assert_eq!(
+            vec!["capture-0001.csv", "capture-0002.csv", "capture-0003.csv"],
+            directory.files()
+        );
+        let first = directory.read("capture-0001.csv");
+        assert_eq!(3, first.lines().count());
+        assert_eq!(
+            vec![record(0, 20_000), record(1_000, 20_000)],
+            read_log(&first)
+        );
+        assert_eq!(
+            vec![record(4_000, 20_000)],
+            read_log(&directory.read("capture-0003.csv"))
+        );
+    }
+
+    #[test]
+    fn rotate_by_time() {
+        let directory = TempDir::new("time");
+        fs::write(directory.0.join("capture-0001.jsonl"), "kept").unwrap();
+        let rotation = Rotation {
+            max_bytes: None,
+            max_duration_ms: Some(60_000),
+        };
+        let mut log = RotatingLog::new(
+            directory.0.join("capture.jsonl"),
+            Format::JsonLines,
+            Unit::Celsius,
+            rotation,
+        );
+
+        for timestamp_ms in &[0, 30_000, 59_999, 60_000, 200_000] {
+            log.write(&record(*timestamp_ms, 20_000)).unwrap();
+        }
+        drop(log);
+
+        assert_eq!(
+            vec![
+                "capture-0001.jsonl",
+                "capture-0002.jsonl",
+                "capture-0003.jsonl",
+                "capture-0004.jsonl"
+            ],
+            directory.files()
+        );
+        assert_eq!("kept", directory.read("capture-0001.jsonl"));
+        assert_eq!(3, read_log(&directory.read("capture-0002.jsonl")).len());
+        assert_eq!(
+            vec![record(60_000, 20_000)],
+            read_log(&directory.read("capture-0003.jsonl"))
+        );
+    }
+}
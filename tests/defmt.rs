@@ -0,0 +1,86 @@
+#[cfg(all(test, feature = "defmt"))]
+mod defmt {
+    use mcp9808::{
+        configuration::{
+            AlertOutputControl, AlertOutputMode, AlertOutputPolarity, AlertOutputSelect,
+            AlertOutputStatus, Configuration, CriticalTemperatureLock, Hysteresis, InterruptClear,
+            ShutdownMode, TemperatureWindowLock,
+        },
+        device_id_revision::{DeviceId, DeviceRevision},
+        manufacturer_id::ManufacturerId,
+        resolution::Resolution,
+        temperature::{Celsius, Millicelsius, TemperatureMeasurement},
+        SlaveAddress,
+    };
+
+    // Format strings are interned into the target's ELF and decoded on the host,
+    // tests check the implementations and the encoded arguments after the string index
+    fn assert_format<T: ::defmt::Format>() {}
+
+    fn encode<T: ::defmt::Format>(value: &T) -> Vec<u8> {
+        value.format(::defmt::export::make_formatter());
+        ::defmt::export::fetch_bytes()
+    }
+
+    #[test]
+    fn public_types_implement_format() {
+        assert_format::<Millicelsius>();
+        assert_format::<Celsius>();
+        assert_format::<TemperatureMeasurement<Millicelsius>>();
+        assert_format::<TemperatureMeasurement<Celsius>>();
+        assert_format::<Configuration>();
+        assert_format::<Hysteresis>();
+        assert_format::<ShutdownMode>();
+        assert_format::<CriticalTemperatureLock>();
+        assert_format::<TemperatureWindowLock>();
+        assert_format::<InterruptClear>();
+        assert_format::<AlertOutputStatus>();
+        assert_format::<AlertOutputControl>();
+        assert_format::<AlertOutputSelect>();
+        assert_format::<AlertOutputPolarity>();
+        assert_format::<AlertOutputMode>();
+        assert_format::<Resolution>();
+        assert_format::<ManufacturerId>();
+        assert_format::<DeviceId>();
+        assert_format::<DeviceRevision>();
+        assert_format::<SlaveAddress>();
+    }
+
+    #[test]
+    fn temperatures() {
+        assert_eq!(
+            &(-3_250i32).to_le_bytes(),
+            &encode(&Millicelsius(-3_250))[2..]
+        );
+        assert_eq!(&21.5f32.to_le_bytes(), &encode(&Celsius(21.5))[2..]);
+    }
+
+    #[test]
+    fn configuration() {
+        let configuration = Configuration {
+            hysteresis: Hysteresis::Deg1_5C,
+            critical_temperature_lock: CriticalTemperatureLock::Locked,
+            alert_output_control: AlertOutputControl::Enabled,
+            alert_output_mode: AlertOutputMode::Interrupt,
+            ..Configuration::default()
+        };
+        let index = ::defmt::export::fetch_string_index();
+        let bytes = encode(&configuration);
+
+        // format string index, hysteresis string index interned first, then CONFIG as one `u16`
+        assert_eq!(6, bytes.len());
+        assert_eq!(&(index + 1).to_le_bytes(), &bytes[..2]);
+        assert_eq!(&index.to_le_bytes(), &bytes[2..4]);
+        assert_eq!(&0x0289u16.to_le_bytes(), &bytes[4..]);
+    }
+
+    #[test]
+    fn slave_address() {
+        let address = SlaveAddress::Alternative {
+            a2: false,
+            a1: false,
+            a0: true,
+        };
+        assert_eq!(&[0x19], &encode(&address)[2..]);
+    }
+}
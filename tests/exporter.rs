@@ -0,0 +1,189 @@
+#[cfg(all(test, feature = "exporter"))]
+mod exporter {
+    use std::{
+        fs,
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpStream,
+        path::PathBuf,
+        process::{Child, Command, Stdio},
+    };
+
+    /// Running exporter, killed on drop
+    struct Exporter {
+        child: Child,
+        config: PathBuf,
+        address: String,
+    }
+
+    impl Exporter {
+        fn start(test: &str, config: &str) -> Self {
+            let path = config_file(test, config);
+            let mut child = Command::new(env!("CARGO_BIN_EXE_mcp9808-exporter"))
+                .args(&["--config", path.to_str().unwrap()])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+                .unwrap();
+            let mut line = String::new();
+            BufReader::new(child.stdout.as_mut().unwrap())
+                .read_line(&mut line)
+                .unwrap();
+            let address = line
+                .trim()
+                .strip_prefix("listening on http://")
+                .and_then(|url| url.strip_suffix("/metrics"))
+                .unwrap_or_else(|| panic!("unexpected output {}", line))
+                .to_string();
+            Exporter {
+                child,
+                config: path,
+                address,
+            }
+        }
+
+        fn get(&self, path: &str) -> String {
+            let mut stream = TcpStream::connect(&self.address).unwrap();
+            write!(stream, "GET {} HTTP/1.1\r\nHost: test\r\n\r\n", path).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        }
+    }
+
+    impl Drop for Exporter {
+        fn drop(&mut self) {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+            let _ = fs::remove_file(&self.config);
+        }
+    }
+
+    fn config_file(test: &str, config: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "mcp9808-exporter-{}-{}.conf",
+            test,
+            std::process::id()
+        ));
+        fs::write(&path, config).unwrap();
+        path
+    }
+
+    #[test]
+    fn metrics() {
+        let exporter = Exporter::start(
+            "metrics",
+            "
+            listen = 127.0.0.1:0
+            interval = 60000
+
+            [sensor rack1-top]
+            device = simulator
+            temperature = -3.25
+            ",
+        );
+
+        let response = exporter.get("/metrics");
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Type: text/plain; version=0.0.4"));
+        for sample in &[
+            "mcp9808_up{sensor=\"rack1-top\",address=\"0x18\"} 1\n",
+            "mcp9808_polls_total{sensor=\"rack1-top\",address=\"0x18\"} 1\n",
+            "mcp9808_read_errors_total{sensor=\"rack1-top\",address=\"0x18\"} 0\n",
+            "mcp9808_temperature_celsius{sensor=\"rack1-top\",address=\"0x18\"} -3.25\n",
+            "mcp9808_alert{sensor=\"rack1-top\",address=\"0x18\",limit=\"lower\"} 1\n",
+            "mcp9808_alert{sensor=\"rack1-top\",address=\"0x18\",limit=\"upper\"} 0\n",
+            "mcp9808_limit_celsius{sensor=\"rack1-top\",address=\"0x18\",limit=\"critical\"} 0\n",
+            "mcp9808_resolution_celsius{sensor=\"rack1-top\",address=\"0x18\"} 0.0625\n",
+            "# TYPE mcp9808_read_errors_total counter\n",
+        ] {
+            assert!(response.contains(sample), "missing {}", sample);
+        }
+    }
+
+    #[test]
+    fn read_errors() {
+        let exporter = Exporter::start(
+            "errors",
+            "
+            listen = 127.0.0.1:0
+            interval = 60000
+
+            [sensor present]
+            device = simulator
+
+            [sensor missing]
+            device = simulator
+            address = 0x19
+            fault = nack
+            ",
+        );
+
+        let response = exporter.get("/metrics");
+
+        assert!(response.contains("mcp9808_up{sensor=\"present\",address=\"0x18\"} 1\n"));
+        assert!(response.contains("mcp9808_up{sensor=\"missing\",address=\"0x19\"} 0\n"));
+        assert!(
+            response.contains("mcp9808_read_errors_total{sensor=\"missing\",address=\"0x19\"} 1\n")
+        );
+        assert!(!response.contains("mcp9808_temperature_celsius{sensor=\"missing\""));
+    }
+
+    #[test]
+    fn simulated_address() {
+        let exporter = Exporter::start(
+            "address",
+            "
+            listen = 127.0.0.1:0
+            interval = 60000
+
+            [sensor second]
+            device = simulator
+            address = 0x19
+            temperature = 21.5
+            ",
+        );
+
+        let response = exporter.get("/metrics");
+
+        assert!(response.contains("mcp9808_up{sensor=\"second\",address=\"0x19\"} 1\n"));
+        assert!(response
+            .contains("mcp9808_temperature_celsius{sensor=\"second\",address=\"0x19\"} 21.5\n"));
+    }
+
+    #[test]
+    fn not_found() {
+        let exporter = Exporter::start(
+            "not-found",
+            "listen = 127.0.0.1:0\n[sensor a]\ndevice = simulator\n",
+        );
+
+        assert!(exporter.get("/").starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+
+    #[test]
+    fn invalid_config() {
+        for (name, config, error) in &[
+            (
+                "invalid-address",
+                "[sensor a]\naddress = 0x20\n",
+                "error: line 2: invalid address, expected 0x18 to 0x1f\n",
+            ),
+            (
+                "invalid-temperature",
+                "[sensor a]\ntemperature = 99999999\n",
+                "error: line 2: invalid temperature 99999999\n",
+            ),
+        ] {
+            let path = config_file(name, config);
+            let output = Command::new(env!("CARGO_BIN_EXE_mcp9808-exporter"))
+                .args(&["--config", path.to_str().unwrap()])
+                .output()
+                .unwrap();
+            fs::remove_file(&path).unwrap();
+
+            assert!(!output.status.success());
+            assert_eq!(*error, String::from_utf8(output.stderr).unwrap());
+        }
+    }
+}
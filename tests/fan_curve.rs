@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod fan_curve {
+    use embedded_hal::PwmPin;
+    use i2c_interface::generic_array::arr;
+    use mcp9808::{
+        fan_curve::{Error, FanCurve, FanCurvePoint},
+        simulator::Simulator,
+        temperature::{Celsius, Millicelsius},
+        SlaveAddress, MCP9808,
+    };
+
+    struct MockPwm {
+        duty: u8,
+    }
+
+    impl PwmPin for MockPwm {
+        type Duty = u8;
+
+        fn disable(&mut self) {}
+
+        fn enable(&mut self) {}
+
+        fn get_duty(&self) -> Self::Duty {
+            self.duty
+        }
+
+        fn get_max_duty(&self) -> Self::Duty {
+            u8::max_value()
+        }
+
+        fn set_duty(&mut self, duty: Self::Duty) {
+            self.duty = duty;
+        }
+    }
+
+    #[test]
+    fn invalid_points() {
+        let unsorted = arr![FanCurvePoint;
+            FanCurvePoint::new(Millicelsius(40_000), 500),
+            FanCurvePoint::new(Millicelsius(40_000), 800)
+        ];
+        assert_eq!(
+            Some(Error::Unsorted),
+            FanCurve::new(unsorted, Millicelsius(0)).err()
+        );
+
+        let out_of_range = arr![FanCurvePoint; FanCurvePoint::new(Millicelsius(40_000), 1_001)];
+        assert_eq!(
+            Some(Error::DutyOutOfRange),
+            FanCurve::new(out_of_range, Millicelsius(0)).err()
+        );
+    }
+
+    #[test]
+    fn interpolation() {
+        let points = arr![FanCurvePoint;
+            FanCurvePoint::new(Celsius(25.0), 0),
+            FanCurvePoint::new(Celsius(35.0), 300),
+            FanCurvePoint::new(Celsius(55.0), 1_000)
+        ];
+        let curve = FanCurve::new(points, Celsius(0.0)).unwrap();
+
+        assert_eq!(0, curve.interpolate(Celsius(-10.0)));
+        assert_eq!(0, curve.interpolate(Celsius(25.0)));
+        assert_eq!(150, curve.interpolate(Celsius(30.0)));
+        assert_eq!(300, curve.interpolate(Celsius(35.0)));
+        assert_eq!(302, curve.interpolate(Millicelsius(35_063)));
+        assert_eq!(650, curve.interpolate(Celsius(45.0)));
+        assert_eq!(1_000, curve.interpolate(Celsius(55.0)));
+        assert_eq!(1_000, curve.interpolate(Celsius(125.0)));
+    }
+
+    #[test]
+    fn hysteresis() {
+        let points = arr![FanCurvePoint;
+            FanCurvePoint::new(Millicelsius(20_000), 0),
+            FanCurvePoint::new(Millicelsius(70_000), 1_000)
+        ];
+        let mut curve = FanCurve::new(points, Millicelsius(2_000)).unwrap();
+
+        assert_eq!(400, curve.duty(Millicelsius(40_000)));
+        assert_eq!(400, curve.duty(Millicelsius(39_000)));
+        assert_eq!(400, curve.duty(Millicelsius(38_000)));
+        assert_eq!(380, curve.duty(Millicelsius(37_000)));
+        assert_eq!(380, curve.duty(Millicelsius(38_500)));
+        assert_eq!(420, curve.duty(Millicelsius(41_000)));
+
+        curve.reset();
+        assert_eq!(380, curve.duty(Millicelsius(39_000)));
+    }
+
+    #[test]
+    fn drive_pwm_pin_from_sensor() {
+        let points = arr![FanCurvePoint;
+            FanCurvePoint::new(Celsius(20.0), 0),
+            FanCurvePoint::new(Celsius(60.0), 1_000)
+        ];
+        let mut curve = FanCurve::new(points, Celsius(1.0)).unwrap();
+        let mut pwm = MockPwm { duty: 0 };
+
+        let mut simulator = Simulator::new(SlaveAddress::Default);
+        simulator.set_ambient_temperature(Celsius(40.0));
+        let mut mcp9808 = MCP9808::new(simulator, SlaveAddress::Default);
+
+        assert_eq!(Ok(500), curve.drive(&mut mcp9808, &mut pwm));
+        assert_eq!(127, pwm.duty);
+    }
+}
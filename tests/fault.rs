@@ -0,0 +1,217 @@
+#[cfg(test)]
+mod fault {
+    use mcp9808::{
+        configuration::{Configuration, Hysteresis},
+        fault::{Error, Fault, FaultyI2c, Trigger},
+        resolution::Resolution,
+        simulator::Simulator,
+        temperature::{Millicelsius, TemperatureMeasurement},
+        verify, Register, SlaveAddress, MCP9808,
+    };
+
+    type Device = MCP9808<FaultyI2c<Simulator>>;
+
+    fn mcp9808(fault: Fault, trigger: Trigger) -> Device {
+        let mut simulator = Simulator::new(SlaveAddress::Default);
+        simulator.set_ambient_temperature(Millicelsius(23_125));
+        MCP9808::new(
+            FaultyI2c::new(simulator, fault, trigger),
+            SlaveAddress::Default,
+        )
+    }
+
+    fn configuration() -> Configuration {
+        Configuration {
+            hysteresis: Hysteresis::Deg1_5C,
+            ..Configuration::default()
+        }
+    }
+
+    /// Calls every `MCP9808` register method, then every write with verification,
+    /// returning which of them failed
+    fn call_all(mcp9808: &mut Device) -> Vec<bool> {
+        let mut failures = vec![
+            mcp9808
+                .read_ambient_temperature::<Millicelsius, _>()
+                .is_err(),
+            mcp9808.read_upper_temperature::<Millicelsius, _>().is_err(),
+            mcp9808
+                .write_upper_temperature(Millicelsius(30_000))
+                .is_err(),
+            mcp9808.read_lower_temperature::<Millicelsius, _>().is_err(),
+            mcp9808
+                .write_lower_temperature(Millicelsius(10_000))
+                .is_err(),
+            mcp9808
+                .read_critical_temperature::<Millicelsius, _>()
+                .is_err(),
+            mcp9808
+                .write_critical_temperature(Millicelsius(40_000))
+                .is_err(),
+            mcp9808.read_configuration().is_err(),
+            mcp9808.write_configuration(configuration()).is_err(),
+            mcp9808.read_resolution().is_err(),
+            mcp9808.write_resolution(Resolution::Deg0_25C).is_err(),
+            mcp9808.read_manufacturer_id().is_err(),
+            mcp9808.read_device_information().is_err(),
+        ];
+        mcp9808.enable_write_verification();
+        failures.extend(vec![
+            mcp9808
+                .write_upper_temperature(Millicelsius(30_000))
+                .is_err(),
+            mcp9808
+                .write_lower_temperature(Millicelsius(10_000))
+                .is_err(),
+            mcp9808
+                .write_critical_temperature(Millicelsius(40_000))
+                .is_err(),
+            mcp9808.write_configuration(configuration()).is_err(),
+            mcp9808.write_resolution(Resolution::Deg0_25C).is_err(),
+        ]);
+        mcp9808.disable_write_verification();
+        failures
+    }
+
+    #[test]
+    fn no_faults() {
+        let mut mcp9808 = mcp9808(Fault::Nack, Trigger::Never);
+        assert!(call_all(&mut mcp9808).iter().all(|failed| !failed));
+        assert_eq!(0, mcp9808.i2c().injected_count());
+        assert_eq!(23, mcp9808.i2c().transaction_count());
+    }
+
+    #[test]
+    fn nack_every_transaction() {
+        let mut mcp9808 = mcp9808(Fault::Nack, Trigger::EveryNth(1));
+        assert!(call_all(&mut mcp9808).iter().all(|failed| *failed));
+        assert_eq!(18, mcp9808.i2c().injected_count());
+
+        assert_eq!(Err(Error::InjectedNack), mcp9808.read_resolution());
+        assert_eq!(
+            Err(verify::Error::I2c(Error::InjectedNack)),
+            mcp9808.write_resolution(Resolution::Deg0_5C)
+        );
+    }
+
+    #[test]
+    fn nack_every_nth_transaction() {
+        let mut mcp9808 = mcp9808(Fault::Nack, Trigger::EveryNth(3));
+        let failures = call_all(&mut mcp9808);
+        assert_eq!(
+            vec![
+                false, false, true, false, false, true, false, false, true, false, false, true,
+                false, true, false, true, false, true
+            ],
+            failures
+        );
+        assert_eq!(7, mcp9808.i2c().injected_count());
+    }
+
+    #[test]
+    fn nack_by_register() {
+        let mut mcp9808 = mcp9808(
+            Fault::Nack,
+            Trigger::Register(Register::CriticalTemperatureRegister),
+        );
+        let failures = call_all(&mut mcp9808);
+        let failed: Vec<usize> = failures
+            .iter()
+            .enumerate()
+            .filter(|(_, failed)| **failed)
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(vec![5, 6, 15], failed);
+    }
+
+    #[test]
+    fn truncated_reads() {
+        let mut mcp9808 = mcp9808(
+            Fault::Truncate(1),
+            Trigger::Register(Register::AmbientTemperatureRegister),
+        );
+        let measurement: TemperatureMeasurement<Millicelsius> =
+            mcp9808.read_ambient_temperature().unwrap();
+        assert_ne!(Millicelsius(23_125), measurement.temperature);
+
+        mcp9808
+            .i2c_mut()
+            .set_trigger(Trigger::Register(Register::ManufacturerIdRegister));
+        assert!(!mcp9808.read_manufacturer_id().unwrap().is_valid());
+    }
+
+    #[test]
+    fn truncated_writes_are_detected() {
+        let mut mcp9808 = mcp9808(Fault::Truncate(1), Trigger::Never);
+        mcp9808
+            .write_upper_temperature(Millicelsius(30_000))
+            .unwrap();
+
+        mcp9808.enable_write_verification();
+        mcp9808
+            .i2c_mut()
+            .set_trigger(Trigger::Register(Register::UpperTemperatureRegister));
+        assert_eq!(
+            Err(verify::Error::VerifyFailed {
+                register: Register::UpperTemperatureRegister,
+                expected: 0x0140,
+                actual: 0x01fc,
+            }),
+            mcp9808.write_upper_temperature(Millicelsius(20_000))
+        );
+
+        // only the MSB of 20°C reached the device, the LSB of 30°C was kept
+        mcp9808.i2c_mut().set_trigger(Trigger::Never);
+        assert_eq!(Ok(Millicelsius(30_000)), mcp9808.read_upper_temperature());
+    }
+
+    #[test]
+    fn corrupted_bytes() {
+        let mut mcp9808 = mcp9808(
+            Fault::Corrupt(0b0000_0001),
+            Trigger::Register(Register::ResolutionRegister),
+        );
+        assert_eq!(Ok(Resolution::Deg0_125C), mcp9808.read_resolution());
+
+        mcp9808.i2c_mut().set_trigger(Trigger::EveryNth(2));
+        assert_eq!(Ok(Resolution::Deg0_0625C), mcp9808.read_resolution());
+        mcp9808.enable_write_verification();
+        assert_eq!(
+            Err(verify::Error::VerifyFailed {
+                register: Register::ResolutionRegister,
+                expected: Resolution::Deg0_5C as u16,
+                actual: Resolution::Deg0_25C as u16,
+            }),
+            mcp9808.write_resolution(Resolution::Deg0_5C)
+        );
+    }
+
+    #[test]
+    fn random_faults_are_reproducible() {
+        let trigger = Trigger::Random {
+            seed: 42,
+            per_mille: 300,
+        };
+        let mut first = mcp9808(Fault::Nack, trigger);
+        let mut second = mcp9808(Fault::Nack, trigger);
+        let failures = call_all(&mut first);
+
+        assert_eq!(failures, call_all(&mut second));
+        assert!(failures.iter().any(|failed| *failed));
+        assert!(failures.iter().any(|failed| !failed));
+    }
+
+    #[test]
+    fn random_corruption_never_panics() {
+        for seed in 1..50 {
+            let mut mcp9808 = mcp9808(
+                Fault::Corrupt(0xFF),
+                Trigger::Random {
+                    seed,
+                    per_mille: 500,
+                },
+            );
+            call_all(&mut mcp9808);
+        }
+    }
+}
@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod filter {
+    use i2c_interface::generic_array::typenum::consts::{U3, U4};
+    use mcp9808::{
+        filter::{Error, ExponentialMovingAverage, Filter, FilteredSensor, Median, MovingAverage},
+        simulator::Simulator,
+        temperature::{Celsius, Millicelsius, TemperatureMeasurement},
+        SlaveAddress, MCP9808,
+    };
+
+    fn filter_all<F: Filter>(filter: &mut F, samples: &[i32]) -> Vec<i32> {
+        samples
+            .iter()
+            .map(|sample| filter.filter(Millicelsius(*sample)).0)
+            .collect()
+    }
+
+    #[test]
+    fn moving_average() {
+        let mut filter = MovingAverage::<U4>::new();
+        assert_eq!(
+            vec![20_000, 20_031, 20_041, 20_047, 20_062, 20_062],
+            filter_all(
+                &mut filter,
+                &[20_000, 20_062, 20_062, 20_062, 20_062, 20_062]
+            )
+        );
+
+        filter.reset();
+        assert_eq!(
+            vec![-1_000, -1_500],
+            filter_all(&mut filter, &[-1_000, -2_000])
+        );
+    }
+
+    #[test]
+    fn median() {
+        let mut filter = Median::<U3>::new();
+        assert_eq!(
+            vec![20_000, 20_031, 20_062, 20_125, 20_125],
+            filter_all(&mut filter, &[20_000, 20_062, 85_000, 20_125, 20_125])
+        );
+
+        filter.reset();
+        assert_eq!(vec![-500], filter_all(&mut filter, &[-500]));
+    }
+
+    #[test]
+    fn exponential_moving_average() {
+        let mut filter = ExponentialMovingAverage::new(250).unwrap();
+        assert_eq!(
+            vec![20_000, 20_250, 20_438, 20_579],
+            filter_all(&mut filter, &[20_000, 21_000, 21_000, 21_000])
+        );
+
+        let mut filter = ExponentialMovingAverage::new(1_000).unwrap();
+        assert_eq!(
+            vec![20_000, 21_000, -3_000],
+            filter_all(&mut filter, &[20_000, 21_000, -3_000])
+        );
+
+        for &alpha in &[0, 1_001] {
+            assert_eq!(
+                Err(Error::AlphaOutOfRange),
+                ExponentialMovingAverage::new(alpha).map(|_| ())
+            );
+        }
+    }
+
+    #[test]
+    fn filtered_sensor() {
+        let mut simulator = Simulator::new(SlaveAddress::Default);
+        simulator.set_ambient_temperature(Millicelsius(24_000));
+        let mcp9808 = MCP9808::new(simulator, SlaveAddress::Default);
+        let mut sensor = FilteredSensor::new(mcp9808, MovingAverage::<U4>::new());
+
+        let measurement: TemperatureMeasurement<Celsius> =
+            sensor.read_ambient_temperature().unwrap();
+        assert_eq!(Celsius(24.0), measurement.temperature);
+        assert!(measurement.is_critical);
+
+        let (mcp9808, filter) = sensor.release();
+        let mut simulator = mcp9808.release();
+        simulator.set_ambient_temperature(Millicelsius(25_000));
+        let mcp9808 = MCP9808::new(simulator, SlaveAddress::Default);
+        let mut sensor = FilteredSensor::new(mcp9808, filter);
+
+        let measurement: TemperatureMeasurement<Millicelsius> =
+            sensor.read_ambient_temperature().unwrap();
+        assert_eq!(Millicelsius(24_500), measurement.temperature);
+
+        sensor.reset();
+        let measurement: TemperatureMeasurement<Millicelsius> =
+            sensor.read_ambient_temperature().unwrap();
+        assert_eq!(Millicelsius(25_000), measurement.temperature);
+    }
+}
@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod health {
+    use embedded_hal::blocking::i2c;
+    use mcp9808::{
+        health::HealthMonitor,
+        resolution::Resolution,
+        simulator::{Error, Simulator},
+        temperature::{Celsius, Millicelsius, TemperatureMeasurement},
+        SlaveAddress, MCP9808,
+    };
+
+    struct ZeroI2c;
+
+    impl i2c::WriteRead for ZeroI2c {
+        type Error = ();
+
+        fn write_read(&mut self, _: u8, _: &[u8], buffer: &mut [u8]) -> Result<(), Self::Error> {
+            for byte in buffer.iter_mut() {
+                *byte = 0;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn stuck_readings() {
+        let mut monitor = HealthMonitor::new(Resolution::Deg0_0625C);
+        monitor.set_stuck_threshold(3);
+
+        assert!(!monitor.check_temperature(Millicelsius(21_062)).stuck);
+        assert!(!monitor.check_temperature(Millicelsius(21_062)).stuck);
+        assert!(monitor.check_temperature(Millicelsius(21_062)).stuck);
+        assert!(!monitor.health().is_healthy());
+        assert!(!monitor.check_temperature(Millicelsius(21_125)).stuck);
+    }
+
+    #[test]
+    fn implausible_jumps() {
+        let mut monitor = HealthMonitor::new(Resolution::Deg0_0625C);
+        monitor.set_max_jump(Celsius(2.0));
+
+        assert!(!monitor.check_temperature(Celsius(20.0)).implausible_jump);
+        assert!(!monitor.check_temperature(Celsius(22.0)).implausible_jump);
+        assert!(monitor.check_temperature(Celsius(-2.0)).implausible_jump);
+        assert!(!monitor.check_temperature(Celsius(-2.5)).implausible_jump);
+    }
+
+    #[test]
+    fn default_max_jump_follows_resolution() {
+        let mut monitor = HealthMonitor::new(Resolution::Deg0_0625C);
+        assert_eq!(Millicelsius(10_000), monitor.max_jump());
+        assert!(!monitor.check_temperature(Celsius(20.0)).implausible_jump);
+        assert!(!monitor.check_temperature(Celsius(28.0)).implausible_jump);
+
+        monitor.set_resolution(Resolution::Deg0_5C);
+        assert_eq!(Millicelsius(1_200), monitor.max_jump());
+        assert!(monitor.check_temperature(Celsius(20.0)).implausible_jump);
+        assert!(!monitor.check_temperature(Celsius(21.0)).implausible_jump);
+
+        monitor.set_max_jump(Celsius(5.0));
+        monitor.set_resolution(Resolution::Deg0_25C);
+        assert_eq!(Millicelsius(5_000), monitor.max_jump());
+    }
+
+    #[test]
+    fn readings_finer_than_resolution() {
+        let mut monitor = HealthMonitor::new(Resolution::Deg0_25C);
+        monitor.set_max_jump(Celsius(200.0));
+
+        assert!(
+            !monitor
+                .check_temperature(Millicelsius(20_250))
+                .implausible_jump
+        );
+        assert!(
+            !monitor
+                .check_temperature(Millicelsius(-750))
+                .implausible_jump
+        );
+        assert!(
+            monitor
+                .check_temperature(Millicelsius(-812))
+                .implausible_jump
+        );
+        assert!(
+            monitor
+                .check_temperature(Millicelsius(20_125))
+                .implausible_jump
+        );
+
+        monitor.set_resolution(Resolution::Deg0_125C);
+        assert!(
+            !monitor
+                .check_temperature(Millicelsius(20_125))
+                .implausible_jump
+        );
+    }
+
+    #[test]
+    fn out_of_range() {
+        let mut monitor = HealthMonitor::new(Resolution::Deg0_0625C);
+        monitor.set_max_jump(Celsius(200.0));
+
+        assert!(!monitor.check_temperature(Celsius(-40.0)).out_of_range);
+        assert!(!monitor.check_temperature(Celsius(125.0)).out_of_range);
+        assert!(monitor.check_temperature(Celsius(125.0625)).out_of_range);
+        assert!(monitor.check_temperature(Celsius(-40.5)).out_of_range);
+    }
+
+    #[test]
+    fn bus_errors() {
+        let simulator = Simulator::new(SlaveAddress::Alternative {
+            a2: false,
+            a1: false,
+            a0: true,
+        });
+        let mut mcp9808 = MCP9808::new(simulator, SlaveAddress::Default);
+        let mut monitor = HealthMonitor::new(Resolution::Deg0_0625C);
+
+        for _ in 0..2 {
+            let result: Result<TemperatureMeasurement<Millicelsius>, Error> =
+                monitor.read_ambient_temperature(&mut mcp9808);
+            assert_eq!(Err(Error::AddressNack), result);
+        }
+        assert_eq!(2, monitor.health().consecutive_bus_errors);
+
+        let mut mcp9808 = MCP9808::new(
+            mcp9808.release(),
+            SlaveAddress::Alternative {
+                a2: false,
+                a1: false,
+                a0: true,
+            },
+        );
+        let result: Result<TemperatureMeasurement<Millicelsius>, Error> =
+            monitor.read_ambient_temperature(&mut mcp9808);
+        assert!(result.is_ok());
+
+        let health = monitor.health();
+        assert_eq!(0, health.consecutive_bus_errors);
+        assert_eq!(2, health.total_bus_errors);
+        assert!(health.is_healthy());
+    }
+
+    #[test]
+    fn identity() {
+        let mut monitor = HealthMonitor::new(Resolution::Deg0_0625C);
+
+        let mut mcp9808 =
+            MCP9808::new(Simulator::new(SlaveAddress::Default), SlaveAddress::Default);
+        assert_eq!(Ok(true), monitor.check_identity(&mut mcp9808));
+        assert!(!monitor.health().identity_mismatch);
+
+        let mut mcp9808 = MCP9808::new(ZeroI2c, SlaveAddress::Default);
+        assert_eq!(Ok(false), monitor.check_identity(&mut mcp9808));
+        assert!(monitor.health().identity_mismatch);
+
+        monitor.reset();
+        assert!(monitor.health().is_healthy());
+    }
+}
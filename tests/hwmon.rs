@@ -0,0 +1,151 @@
+#[cfg(all(test, feature = "std"))]
+mod hwmon {
+    use mcp9808::{
+        configuration::Hysteresis,
+        hwmon::{Error, Jc42},
+        temperature::{Celsius, Millicelsius, TemperatureMeasurement},
+    };
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    /// Fake hwmon class directory, removed on drop
+    struct FakeSysfs {
+        root: PathBuf,
+    }
+
+    impl FakeSysfs {
+        fn new(test: &str) -> Self {
+            let root =
+                std::env::temp_dir().join(format!("mcp9808-hwmon-{}-{}", test, std::process::id()));
+            let _ = fs::remove_dir_all(&root);
+            fs::create_dir_all(&root).unwrap();
+            FakeSysfs { root }
+        }
+
+        fn add_device(&self, device: &str, name: &str) -> PathBuf {
+            let path = self.root.join(device);
+            fs::create_dir_all(&path).unwrap();
+            for (attribute, value) in &[
+                ("name", name),
+                ("temp1_input", "25250"),
+                ("temp1_max", "30000"),
+                ("temp1_min", "-5000"),
+                ("temp1_crit", "80000"),
+                ("temp1_crit_hyst", "78500"),
+                ("temp1_crit_alarm", "0"),
+                ("temp1_max_alarm", "0"),
+                ("temp1_min_alarm", "0"),
+            ] {
+                write(&path, attribute, value);
+            }
+            path
+        }
+    }
+
+    impl Drop for FakeSysfs {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+
+    fn write(path: &Path, attribute: &str, value: &str) {
+        fs::write(path.join(attribute), format!("{}\n", value)).unwrap();
+    }
+
+    fn read(path: &Path, attribute: &str) -> String {
+        fs::read_to_string(path.join(attribute)).unwrap()
+    }
+
+    #[test]
+    fn find_jc42_devices() {
+        let sysfs = FakeSysfs::new("find");
+        let second = sysfs.add_device("hwmon3", "jc42");
+        sysfs.add_device("hwmon0", "coretemp");
+        let first = sysfs.add_device("hwmon1", "jc42");
+
+        let devices = Jc42::find(&sysfs.root).unwrap();
+
+        assert_eq!(vec![Jc42::new(first), Jc42::new(second)], devices);
+    }
+
+    #[test]
+    fn read_ambient_temperature() {
+        let sysfs = FakeSysfs::new("ambient");
+        let path = sysfs.add_device("hwmon0", "jc42");
+        write(&path, "temp1_max_alarm", "1");
+        let mut sensor = Jc42::new(&path);
+
+        let measurement: TemperatureMeasurement<Millicelsius> =
+            sensor.read_ambient_temperature().unwrap();
+
+        assert_eq!(Millicelsius(25_250), measurement.temperature);
+        assert!(!measurement.is_critical);
+        assert!(measurement.is_upper);
+        assert!(!measurement.is_lower);
+    }
+
+    #[test]
+    fn read_and_write_limits() {
+        let sysfs = FakeSysfs::new("limits");
+        let path = sysfs.add_device("hwmon0", "jc42");
+        let mut sensor = Jc42::new(&path);
+
+        assert_eq!(
+            Millicelsius(30_000),
+            sensor.read_upper_temperature::<Millicelsius>().unwrap()
+        );
+        assert_eq!(
+            Millicelsius(-5_000),
+            sensor.read_lower_temperature::<Millicelsius>().unwrap()
+        );
+        assert_eq!(
+            Celsius(80.0),
+            sensor.read_critical_temperature::<Celsius>().unwrap()
+        );
+
+        sensor.write_upper_temperature(Celsius(40.5)).unwrap();
+        sensor
+            .write_lower_temperature(Millicelsius(-10_250))
+            .unwrap();
+        sensor.write_critical_temperature(Celsius(90.0)).unwrap();
+
+        assert_eq!("40500", read(&path, "temp1_max"));
+        assert_eq!("-10250", read(&path, "temp1_min"));
+        assert_eq!("90000", read(&path, "temp1_crit"));
+    }
+
+    #[test]
+    fn read_and_write_hysteresis() {
+        let sysfs = FakeSysfs::new("hysteresis");
+        let path = sysfs.add_device("hwmon0", "jc42");
+        let mut sensor = Jc42::new(&path);
+
+        assert_eq!(Hysteresis::Deg1_5C, sensor.read_hysteresis().unwrap());
+
+        sensor.write_hysteresis(Hysteresis::Deg6_0C).unwrap();
+
+        assert_eq!("74000", read(&path, "temp1_crit_hyst"));
+        assert_eq!(Hysteresis::Deg6_0C, sensor.read_hysteresis().unwrap());
+    }
+
+    #[test]
+    fn invalid_attributes() {
+        let sysfs = FakeSysfs::new("invalid");
+        let path = sysfs.add_device("hwmon0", "jc42");
+        let mut sensor = Jc42::new(&path);
+
+        write(&path, "temp1_crit_hyst", "79000");
+        assert!(matches!(sensor.read_hysteresis(), Err(Error::InvalidValue)));
+
+        write(&path, "temp1_input", "warm");
+        let result: Result<TemperatureMeasurement<Millicelsius>, _> =
+            sensor.read_ambient_temperature();
+        assert!(matches!(result, Err(Error::InvalidValue)));
+
+        fs::remove_file(path.join("temp1_max")).unwrap();
+        let result: Result<Millicelsius, _> = sensor.read_upper_temperature();
+        assert!(matches!(result, Err(Error::Io(_))));
+    }
+}
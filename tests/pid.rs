@@ -0,0 +1,217 @@
+#[cfg(test)]
+mod pid {
+    use core::cell::Cell;
+    use embedded_hal::PwmPin;
+    use mcp9808::{
+        clock::Clock,
+        pid::{Error, Gains, PidController},
+        simulator::Simulator,
+        temperature::{Millicelsius, TemperatureMeasurement},
+        thermostat::Polarity,
+        SlaveAddress, MCP9808,
+    };
+
+    struct MockPwm {
+        duty: u16,
+    }
+
+    impl PwmPin for MockPwm {
+        type Duty = u16;
+
+        fn disable(&mut self) {}
+
+        fn enable(&mut self) {}
+
+        fn get_duty(&self) -> Self::Duty {
+            self.duty
+        }
+
+        fn get_max_duty(&self) -> Self::Duty {
+            1_000
+        }
+
+        fn set_duty(&mut self, duty: Self::Duty) {
+            self.duty = duty;
+        }
+    }
+
+    #[derive(Default)]
+    struct MockClock(Cell<u64>);
+
+    impl MockClock {
+        fn advance(&self, milliseconds: u64) {
+            self.0.set(self.0.get() + milliseconds);
+        }
+    }
+
+    impl Clock for MockClock {
+        fn now_ms(&self) -> u64 {
+            self.0.get()
+        }
+    }
+
+    fn measurement(millicelsius: i32) -> TemperatureMeasurement<Millicelsius> {
+        TemperatureMeasurement {
+            temperature: Millicelsius(millicelsius),
+            is_critical: false,
+            is_upper: false,
+            is_lower: false,
+        }
+    }
+
+    #[test]
+    fn proportional_output_is_clamped() {
+        let clock = MockClock::default();
+        let gains = Gains {
+            kp: 0.1,
+            ki: 0.0,
+            kd: 0.0,
+        };
+        let mut pid = PidController::new(
+            MockPwm { duty: 0 },
+            &clock,
+            Millicelsius(40_000),
+            gains,
+            Polarity::Heating,
+        );
+
+        assert_eq!(0.5, pid.update(measurement(35_000)));
+        assert_eq!(1.0, pid.update(measurement(20_000)));
+        assert_eq!(0.0, pid.update(measurement(45_000)));
+
+        assert_eq!(Ok(()), pid.set_output_limits(0.2, 0.8));
+        assert_eq!(0.8, pid.update(measurement(20_000)));
+        assert_eq!(0.2, pid.update(measurement(45_000)));
+
+        let (pwm, _) = pid.release();
+        assert_eq!(200, pwm.duty);
+    }
+
+    #[test]
+    fn invalid_output_limits() {
+        let gains = Gains {
+            kp: 0.1,
+            ki: 0.0,
+            kd: 0.0,
+        };
+        let mut pid = PidController::new(
+            MockPwm { duty: 0 },
+            MockClock::default(),
+            Millicelsius(40_000),
+            gains,
+            Polarity::Heating,
+        );
+
+        for &(min, max) in &[(0.8, 0.2), (-0.1, 0.5), (0.5, 1.1), (f32::NAN, 1.0)] {
+            assert_eq!(
+                Err(Error::InvalidOutputLimits),
+                pid.set_output_limits(min, max)
+            );
+        }
+        assert_eq!(1.0, pid.update(measurement(20_000)));
+    }
+
+    #[test]
+    fn cooling_reverses_error() {
+        let clock = MockClock::default();
+        let gains = Gains {
+            kp: 0.1,
+            ki: 0.0,
+            kd: 0.0,
+        };
+        let mut pid = PidController::new(
+            MockPwm { duty: 0 },
+            &clock,
+            Millicelsius(30_000),
+            gains,
+            Polarity::Cooling,
+        );
+
+        assert_eq!(0.0, pid.update(measurement(25_000)));
+        assert_eq!(0.25, pid.update(measurement(32_500)));
+    }
+
+    #[test]
+    fn integral_does_not_wind_up() {
+        let clock = MockClock::default();
+        let gains = Gains {
+            kp: 0.0,
+            ki: 0.1,
+            kd: 0.0,
+        };
+        let mut pid = PidController::new(
+            MockPwm { duty: 0 },
+            &clock,
+            Millicelsius(40_000),
+            gains,
+            Polarity::Heating,
+        );
+
+        pid.update(measurement(20_000));
+        for _ in 0..100 {
+            clock.advance(1_000);
+            assert_eq!(1.0, pid.update(measurement(20_000)));
+        }
+
+        clock.advance(1_000);
+        assert!(pid.update(measurement(41_000)) < 1.0);
+    }
+
+    #[test]
+    fn derivative_on_measurement() {
+        let clock = MockClock::default();
+        let gains = Gains {
+            kp: 0.0,
+            ki: 0.0,
+            kd: 0.5,
+        };
+        let mut pid = PidController::new(
+            MockPwm { duty: 0 },
+            &clock,
+            Millicelsius(40_000),
+            gains,
+            Polarity::Heating,
+        );
+
+        pid.update(measurement(30_000));
+        clock.advance(1_000);
+        pid.set_setpoint(Millicelsius(50_000));
+        assert_eq!(0.0, pid.update(measurement(30_000)));
+
+        clock.advance(1_000);
+        assert_eq!(0.5, pid.update(measurement(29_000)));
+    }
+
+    #[test]
+    fn closed_loop_with_simulator() {
+        let clock = MockClock::default();
+        let gains = Gains {
+            kp: 0.2,
+            ki: 0.01,
+            kd: 0.5,
+        };
+        let mut pid = PidController::new(
+            MockPwm { duty: 0 },
+            &clock,
+            Millicelsius(37_000),
+            gains,
+            Polarity::Heating,
+        );
+        let mut mcp9808 =
+            MCP9808::new(Simulator::new(SlaveAddress::Default), SlaveAddress::Default);
+
+        let ambient = 20.0;
+        let mut temperature = ambient;
+        for _ in 0..3_000 {
+            let mut simulator = mcp9808.release();
+            simulator.set_ambient_temperature(Millicelsius((temperature * 1_000.0) as i32));
+            mcp9808 = MCP9808::new(simulator, SlaveAddress::Default);
+
+            let duty = pid.step(&mut mcp9808).unwrap();
+            temperature += 0.5 * duty - 0.01 * (temperature - ambient);
+            clock.advance(1_000);
+        }
+
+        assert!((temperature - 37.0f32).abs() < 0.25);
+    }
+}
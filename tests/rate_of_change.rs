@@ -0,0 +1,156 @@
+#[cfg(test)]
+mod rate_of_change {
+    use core::cell::Cell;
+    use i2c_interface::generic_array::typenum::consts::{U2, U4};
+    use mcp9808::{
+        clock::Clock,
+        rate_of_change::{MillicelsiusPerMinute, RateOfChange, RunawayDetector, RunawayEvent},
+        simulator::Simulator,
+        temperature::{Celsius, Millicelsius, TemperatureMeasurement},
+        SlaveAddress, MCP9808,
+    };
+
+    #[derive(Default)]
+    struct MockClock(Cell<u64>);
+
+    impl MockClock {
+        fn advance(&self, milliseconds: u64) {
+            self.0.set(self.0.get() + milliseconds);
+        }
+    }
+
+    impl Clock for MockClock {
+        fn now_ms(&self) -> u64 {
+            self.0.get()
+        }
+    }
+
+    fn measurement(millicelsius: i32) -> TemperatureMeasurement<Millicelsius> {
+        TemperatureMeasurement {
+            temperature: Millicelsius(millicelsius),
+            is_critical: false,
+            is_upper: false,
+            is_lower: false,
+        }
+    }
+
+    #[test]
+    fn rate_over_window() {
+        let clock = MockClock::default();
+        let mut rate_of_change = RateOfChange::<_, U4>::new(&clock);
+
+        assert_eq!(None, rate_of_change.update(measurement(20_000)));
+        assert_eq!(None, rate_of_change.update(measurement(20_000)));
+
+        clock.advance(10_000);
+        assert_eq!(
+            Some(MillicelsiusPerMinute(3_000)),
+            rate_of_change.update(measurement(20_500))
+        );
+
+        clock.advance(10_000);
+        rate_of_change.update(measurement(20_500));
+        clock.advance(10_000);
+        rate_of_change.update(measurement(19_500));
+        assert_eq!(Some(MillicelsiusPerMinute(-1_000)), rate_of_change.rate());
+
+        rate_of_change.reset();
+        assert_eq!(None, rate_of_change.rate());
+    }
+
+    #[test]
+    fn celsius_measurements() {
+        let clock = MockClock::default();
+        let mut rate_of_change = RateOfChange::<_, U2>::new(&clock);
+        rate_of_change.update(TemperatureMeasurement {
+            temperature: Celsius(-10.0),
+            is_critical: false,
+            is_upper: false,
+            is_lower: true,
+        });
+        clock.advance(30_000);
+        let rate = rate_of_change.update(TemperatureMeasurement {
+            temperature: Celsius(-12.5),
+            is_critical: false,
+            is_upper: false,
+            is_lower: true,
+        });
+        assert_eq!(Some(MillicelsiusPerMinute(-5_000)), rate);
+    }
+
+    #[test]
+    fn runaway_detected_after_duration() {
+        let clock = MockClock::default();
+        let mut detector =
+            RunawayDetector::<_, U2>::new(&clock, MillicelsiusPerMinute(2_000), 30_000);
+
+        let mut temperature = 40_000;
+        assert_eq!(None, detector.update(measurement(temperature)));
+        for _ in 0..3 {
+            clock.advance(10_000);
+            temperature += 1_000;
+            assert_eq!(None, detector.update(measurement(temperature)));
+        }
+
+        clock.advance(10_000);
+        temperature += 1_000;
+        assert_eq!(
+            Some(RunawayEvent::Detected {
+                rate: MillicelsiusPerMinute(6_000),
+                since_ms: 10_000
+            }),
+            detector.update(measurement(temperature))
+        );
+        assert!(detector.is_runaway());
+
+        clock.advance(10_000);
+        temperature += 1_000;
+        assert_eq!(None, detector.update(measurement(temperature)));
+
+        clock.advance(10_000);
+        assert_eq!(
+            Some(RunawayEvent::Cleared),
+            detector.update(measurement(temperature))
+        );
+        assert!(!detector.is_runaway());
+    }
+
+    #[test]
+    fn short_spike_is_ignored() {
+        let clock = MockClock::default();
+        let mut detector =
+            RunawayDetector::<_, U2>::new(&clock, MillicelsiusPerMinute(2_000), 30_000);
+
+        detector.update(measurement(40_000));
+        clock.advance(10_000);
+        assert_eq!(None, detector.update(measurement(41_000)));
+        clock.advance(10_000);
+        assert_eq!(None, detector.update(measurement(41_000)));
+        clock.advance(30_000);
+        assert_eq!(None, detector.update(measurement(42_000)));
+        assert!(!detector.is_runaway());
+    }
+
+    #[test]
+    fn step_reads_sensor() {
+        let clock = MockClock::default();
+        let mut detector = RunawayDetector::<_, U2>::new(&clock, MillicelsiusPerMinute(2_000), 0);
+        let mut mcp9808 =
+            MCP9808::new(Simulator::new(SlaveAddress::Default), SlaveAddress::Default);
+
+        assert_eq!(Ok(None), detector.step(&mut mcp9808));
+
+        let mut simulator = mcp9808.release();
+        simulator.set_ambient_temperature(Millicelsius(1_000));
+        let mut mcp9808 = MCP9808::new(simulator, SlaveAddress::Default);
+        clock.advance(15_000);
+
+        assert_eq!(
+            Ok(Some(RunawayEvent::Detected {
+                rate: MillicelsiusPerMinute(4_000),
+                since_ms: 15_000
+            })),
+            detector.step(&mut mcp9808)
+        );
+    }
+}
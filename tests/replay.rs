@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod replay {
+    use mcp9808::{
+        configuration::{AlertOutputControl, Configuration},
+        replay::{Error, Record, ReplayI2c},
+        resolution::Resolution,
+        simulator::Simulator,
+        temperature::{Millicelsius, TemperatureMeasurement},
+        trace::{Operation, TracingI2c, Transaction},
+        verify, SlaveAddress, MCP9808,
+    };
+
+    fn measurement(
+        temperature: i32,
+        is_upper: bool,
+        is_lower: bool,
+    ) -> TemperatureMeasurement<Millicelsius> {
+        TemperatureMeasurement {
+            temperature: Millicelsius(temperature),
+            is_critical: false,
+            is_upper,
+            is_lower,
+        }
+    }
+
+    #[test]
+    fn field_captured_alert_window() {
+        let trace = include_str!("traces/alert_window.trace");
+        let mut mcp9808 = MCP9808::new(ReplayI2c::new(trace), SlaveAddress::Default);
+
+        assert!(mcp9808.read_manufacturer_id().unwrap().is_valid());
+        assert!(mcp9808.read_device_information().unwrap().0.is_valid());
+        mcp9808.write_resolution(Resolution::Deg0_25C).unwrap();
+        mcp9808
+            .write_upper_temperature(Millicelsius(30_000))
+            .unwrap();
+        mcp9808.write_lower_temperature(Millicelsius(0)).unwrap();
+        mcp9808
+            .write_critical_temperature(Millicelsius(40_000))
+            .unwrap();
+        mcp9808
+            .write_configuration(Configuration {
+                alert_output_control: AlertOutputControl::Enabled,
+                ..Configuration::default()
+            })
+            .unwrap();
+
+        assert_eq!(
+            Ok(measurement(23_250, false, false)),
+            mcp9808.read_ambient_temperature()
+        );
+        assert_eq!(
+            Ok(measurement(31_250, true, false)),
+            mcp9808.read_ambient_temperature()
+        );
+        assert_eq!(
+            Ok(measurement(-9_750, false, true)),
+            mcp9808.read_ambient_temperature()
+        );
+        assert_eq!(
+            Err(Error::Recorded { line: 12 }),
+            mcp9808.read_ambient_temperature::<Millicelsius, _>()
+        );
+        assert_eq!(
+            Ok(measurement(23_000, false, false)),
+            mcp9808.read_ambient_temperature()
+        );
+        assert!(mcp9808.i2c().is_finished());
+        assert_eq!(
+            Err(Error::EndOfTrace),
+            mcp9808.read_ambient_temperature::<Millicelsius, _>()
+        );
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let mut trace = String::new();
+        {
+            let mut simulator = Simulator::new(SlaveAddress::Default);
+            simulator.set_ambient_temperature(Millicelsius(-1_500));
+            let bus = TracingI2c::new(simulator, |transaction| {
+                trace.push_str(&Record(transaction).to_string());
+                trace.push('\n');
+            });
+            let mut mcp9808 = MCP9808::new(bus, SlaveAddress::Default);
+            mcp9808.write_resolution(Resolution::Deg0_5C).unwrap();
+            mcp9808
+                .read_ambient_temperature::<Millicelsius, _>()
+                .unwrap();
+        }
+        assert_eq!("w 18 08 00 ok\nr 18 05 3f e8 ok\n", trace);
+
+        let mut mcp9808 = MCP9808::new(ReplayI2c::new(&trace), SlaveAddress::Default);
+        mcp9808.write_resolution(Resolution::Deg0_5C).unwrap();
+        assert_eq!(
+            Ok(TemperatureMeasurement {
+                temperature: Millicelsius(-1_500),
+                is_critical: false,
+                is_upper: false,
+                is_lower: true,
+            }),
+            mcp9808.read_ambient_temperature()
+        );
+        assert!(mcp9808.i2c().is_finished());
+    }
+
+    #[test]
+    fn mismatched_request() {
+        let trace = "w 18 08 01 ok\n";
+        let mut mcp9808 = MCP9808::new(ReplayI2c::new(trace), SlaveAddress::Default);
+
+        assert_eq!(
+            Err(verify::Error::I2c(Error::Mismatch {
+                line: 1,
+                expected: Transaction {
+                    operation: Operation::Write,
+                    address: 0x18,
+                    pointer: Some(0x08),
+                    data: [0x01, 0x00],
+                    len: 1,
+                    ok: true,
+                },
+                actual: Transaction {
+                    operation: Operation::Write,
+                    address: 0x18,
+                    pointer: Some(0x08),
+                    data: [0x03, 0x00],
+                    len: 1,
+                    ok: true,
+                },
+            })),
+            mcp9808.write_resolution(Resolution::Deg0_0625C)
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!("w 18 08 01 ok".parse::<Record>().is_ok());
+        assert!("r 18 - ok".parse::<Record>().is_ok());
+        assert!("x 18 08 ok".parse::<Record>().is_err());
+        assert!("w 18 08 01".parse::<Record>().is_err());
+        assert!("w 18 08 zz ok".parse::<Record>().is_err());
+        assert!("r 18 05 01 02 03 ok".parse::<Record>().is_err());
+        assert!("r 18 05 01 02 ok 03".parse::<Record>().is_err());
+
+        let trace = "# comment\n\nr 18 05 0g 00 ok\n";
+        let mut mcp9808 = MCP9808::new(ReplayI2c::new(trace), SlaveAddress::Default);
+        assert_eq!(Err(Error::Parse { line: 3 }), mcp9808.read_configuration());
+    }
+}
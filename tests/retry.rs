@@ -0,0 +1,177 @@
+#[cfg(test)]
+mod retry {
+    use embedded_hal::blocking::{delay::DelayUs, i2c};
+    use mcp9808::{
+        retry::{RetryI2c, RetryPolicy},
+        simulator::Simulator,
+        temperature::Millicelsius,
+        SlaveAddress, MCP9808,
+    };
+
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    enum BusError {
+        Nack,
+        Arbitration,
+    }
+
+    struct FlakyBus {
+        simulator: Simulator,
+        failures: u32,
+        error: BusError,
+        attempts: u32,
+    }
+
+    impl FlakyBus {
+        fn new(failures: u32, error: BusError) -> Self {
+            FlakyBus {
+                simulator: Simulator::new(SlaveAddress::Default),
+                failures,
+                error,
+                attempts: 0,
+            }
+        }
+
+        fn fail(&mut self) -> Result<(), BusError> {
+            self.attempts += 1;
+            if self.failures > 0 {
+                self.failures -= 1;
+                return Err(self.error);
+            }
+            Ok(())
+        }
+    }
+
+    impl i2c::Write for FlakyBus {
+        type Error = BusError;
+
+        fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+            self.fail()?;
+            self.simulator
+                .write(address, bytes)
+                .map_err(|_| BusError::Nack)
+        }
+    }
+
+    impl i2c::WriteRead for FlakyBus {
+        type Error = BusError;
+
+        fn write_read(
+            &mut self,
+            address: u8,
+            bytes: &[u8],
+            buffer: &mut [u8],
+        ) -> Result<(), Self::Error> {
+            self.fail()?;
+            self.simulator
+                .write_read(address, bytes, buffer)
+                .map_err(|_| BusError::Nack)
+        }
+    }
+
+    #[derive(Default)]
+    struct MockDelay {
+        delays: Vec<u32>,
+    }
+
+    impl DelayUs<u32> for MockDelay {
+        fn delay_us(&mut self, us: u32) {
+            self.delays.push(us);
+        }
+    }
+
+    fn is_retryable(error: &BusError) -> bool {
+        *error == BusError::Nack
+    }
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 4,
+            initial_backoff_us: 100,
+            max_backoff_us: 300,
+            timeout_us: 1_000,
+        }
+    }
+
+    #[test]
+    fn recovers_after_retries() {
+        let bus = RetryI2c::new(
+            FlakyBus::new(3, BusError::Nack),
+            MockDelay::default(),
+            policy(),
+            is_retryable,
+        );
+        let mut mcp9808 = MCP9808::new(bus, SlaveAddress::Default);
+
+        mcp9808
+            .write_critical_temperature(Millicelsius(80_000))
+            .unwrap();
+        assert_eq!(
+            Ok(Millicelsius(80_000)),
+            mcp9808.read_critical_temperature()
+        );
+        assert_eq!(3, mcp9808.i2c().retry_count());
+        assert_eq!(0, mcp9808.i2c().failure_count());
+
+        let (bus, delay) = mcp9808.release().release();
+        assert_eq!(5, bus.attempts);
+        assert_eq!(vec![100, 200, 300], delay.delays);
+    }
+
+    #[test]
+    fn permanent_failure() {
+        let bus = RetryI2c::new(
+            FlakyBus::new(10, BusError::Nack),
+            MockDelay::default(),
+            policy(),
+            is_retryable,
+        );
+        let mut mcp9808 = MCP9808::new(bus, SlaveAddress::Default);
+
+        assert_eq!(
+            Err(BusError::Nack),
+            mcp9808.read_upper_temperature::<Millicelsius, _>()
+        );
+        assert_eq!(3, mcp9808.i2c().retry_count());
+        assert_eq!(1, mcp9808.i2c().failure_count());
+
+        mcp9808.i2c_mut().reset_counters();
+        assert_eq!(0, mcp9808.i2c().retry_count());
+        assert_eq!(0, mcp9808.i2c().failure_count());
+    }
+
+    #[test]
+    fn non_retryable_error() {
+        let bus = RetryI2c::new(
+            FlakyBus::new(1, BusError::Arbitration),
+            MockDelay::default(),
+            policy(),
+            is_retryable,
+        );
+        let mut mcp9808 = MCP9808::new(bus, SlaveAddress::Default);
+
+        assert_eq!(Err(BusError::Arbitration), mcp9808.read_resolution());
+        assert_eq!(0, mcp9808.i2c().retry_count());
+        assert_eq!(1, mcp9808.i2c().failure_count());
+        assert!(mcp9808.read_resolution().is_ok());
+    }
+
+    #[test]
+    fn timeout() {
+        let bus = RetryI2c::new(
+            FlakyBus::new(10, BusError::Nack),
+            MockDelay::default(),
+            RetryPolicy {
+                max_attempts: 10,
+                timeout_us: 350,
+                ..policy()
+            },
+            is_retryable,
+        );
+        let mut mcp9808 = MCP9808::new(bus, SlaveAddress::Default);
+
+        assert_eq!(Err(BusError::Nack), mcp9808.read_configuration());
+        let (bus, delay) = mcp9808.release().release();
+        assert_eq!(3, bus.attempts);
+        assert_eq!(vec![100, 200], delay.delays);
+    }
+}
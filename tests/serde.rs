@@ -0,0 +1,269 @@
+#[cfg(all(test, feature = "serde"))]
+mod serde {
+    use mcp9808::{
+        configuration::{
+            AlertOutputControl, AlertOutputMode, AlertOutputPolarity, AlertOutputSelect,
+            AlertOutputStatus, Configuration, CriticalTemperatureLock, Hysteresis, InterruptClear,
+            ShutdownMode, TemperatureWindowLock,
+        },
+        device_id_revision::{DeviceId, DeviceRevision},
+        manufacturer_id::ManufacturerId,
+        resolution::Resolution,
+        temperature::{Celsius, Millicelsius, TemperatureDelta, TemperatureMeasurement},
+        SlaveAddress,
+    };
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    #[test]
+    fn temperatures() {
+        assert_tokens(
+            &Millicelsius(-3_250),
+            &[
+                Token::NewtypeStruct {
+                    name: "Millicelsius",
+                },
+                Token::I32(-3_250),
+            ],
+        );
+        assert_tokens(
+            &Celsius(21.5),
+            &[Token::NewtypeStruct { name: "Celsius" }, Token::F32(21.5)],
+        );
+        assert_tokens(
+            &TemperatureDelta(-1_250),
+            &[
+                Token::NewtypeStruct {
+                    name: "TemperatureDelta",
+                },
+                Token::I32(-1_250),
+            ],
+        );
+        assert_tokens(
+            &TemperatureMeasurement {
+                temperature: Millicelsius(25_250),
+                is_critical: true,
+                is_upper: true,
+                is_lower: false,
+            },
+            &[
+                Token::Struct {
+                    name: "TemperatureMeasurement",
+                    len: 4,
+                },
+                Token::Str("temperature"),
+                Token::NewtypeStruct {
+                    name: "Millicelsius",
+                },
+                Token::I32(25_250),
+                Token::Str("is_critical"),
+                Token::Bool(true),
+                Token::Str("is_upper"),
+                Token::Bool(true),
+                Token::Str("is_lower"),
+                Token::Bool(false),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn configuration() {
+        let configuration = Configuration {
+            hysteresis: Hysteresis::Deg1_5C,
+            alert_output_control: AlertOutputControl::Enabled,
+            ..Configuration::default()
+        };
+
+        assert_tokens(
+            &configuration,
+            &[
+                Token::Struct {
+                    name: "Configuration",
+                    len: 10,
+                },
+                Token::Str("hysteresis"),
+                Token::UnitVariant {
+                    name: "Hysteresis",
+                    variant: "1.5C",
+                },
+                Token::Str("shutdown_mode"),
+                Token::UnitVariant {
+                    name: "ShutdownMode",
+                    variant: "ContinuousConversion",
+                },
+                Token::Str("critical_temperature_lock"),
+                Token::UnitVariant {
+                    name: "CriticalTemperatureLock",
+                    variant: "Unlocked",
+                },
+                Token::Str("temperature_window_lock"),
+                Token::UnitVariant {
+                    name: "TemperatureWindowLock",
+                    variant: "Unlocked",
+                },
+                Token::Str("interrupt_clear"),
+                Token::UnitVariant {
+                    name: "InterruptClear",
+                    variant: "NoEffect",
+                },
+                Token::Str("alert_output_status"),
+                Token::UnitVariant {
+                    name: "AlertOutputStatus",
+                    variant: "NotAsserted",
+                },
+                Token::Str("alert_output_control"),
+                Token::UnitVariant {
+                    name: "AlertOutputControl",
+                    variant: "Enabled",
+                },
+                Token::Str("alert_output_select"),
+                Token::UnitVariant {
+                    name: "AlertOutputSelect",
+                    variant: "UpperLowerCritical",
+                },
+                Token::Str("alert_output_polarity"),
+                Token::UnitVariant {
+                    name: "AlertOutputPolarity",
+                    variant: "ActiveLow",
+                },
+                Token::Str("alert_output_mode"),
+                Token::UnitVariant {
+                    name: "AlertOutputMode",
+                    variant: "Comparator",
+                },
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn enums() {
+        assert_tokens(
+            &Resolution::Deg0_0625C,
+            &[Token::UnitVariant {
+                name: "Resolution",
+                variant: "0.0625C",
+            }],
+        );
+        assert_tokens(
+            &Resolution::Deg0_5C,
+            &[Token::UnitVariant {
+                name: "Resolution",
+                variant: "0.5C",
+            }],
+        );
+        assert_tokens(
+            &Hysteresis::Deg6_0C,
+            &[Token::UnitVariant {
+                name: "Hysteresis",
+                variant: "6.0C",
+            }],
+        );
+        assert_tokens(
+            &AlertOutputMode::Interrupt,
+            &[Token::UnitVariant {
+                name: "AlertOutputMode",
+                variant: "Interrupt",
+            }],
+        );
+        assert_tokens(
+            &AlertOutputPolarity::ActiveHigh,
+            &[Token::UnitVariant {
+                name: "AlertOutputPolarity",
+                variant: "ActiveHigh",
+            }],
+        );
+        assert_tokens(
+            &AlertOutputSelect::CriticalOnly,
+            &[Token::UnitVariant {
+                name: "AlertOutputSelect",
+                variant: "CriticalOnly",
+            }],
+        );
+        assert_tokens(
+            &AlertOutputStatus::Asserted,
+            &[Token::UnitVariant {
+                name: "AlertOutputStatus",
+                variant: "Asserted",
+            }],
+        );
+        assert_tokens(
+            &InterruptClear::Cleared,
+            &[Token::UnitVariant {
+                name: "InterruptClear",
+                variant: "Cleared",
+            }],
+        );
+        assert_tokens(
+            &InterruptClear::NotEffect,
+            &[Token::UnitVariant {
+                name: "InterruptClear",
+                variant: "NoEffect",
+            }],
+        );
+        assert_tokens(
+            &ShutdownMode::Shutdown,
+            &[Token::UnitVariant {
+                name: "ShutdownMode",
+                variant: "Shutdown",
+            }],
+        );
+        assert_tokens(
+            &CriticalTemperatureLock::Locked,
+            &[Token::UnitVariant {
+                name: "CriticalTemperatureLock",
+                variant: "Locked",
+            }],
+        );
+        assert_tokens(
+            &TemperatureWindowLock::Locked,
+            &[Token::UnitVariant {
+                name: "TemperatureWindowLock",
+                variant: "Locked",
+            }],
+        );
+    }
+
+    #[test]
+    fn identification() {
+        assert_tokens(
+            &ManufacturerId(0x0054),
+            &[
+                Token::NewtypeStruct {
+                    name: "ManufacturerId",
+                },
+                Token::U16(0x0054),
+            ],
+        );
+        assert_tokens(
+            &DeviceId(0x04),
+            &[Token::NewtypeStruct { name: "DeviceId" }, Token::U8(0x04)],
+        );
+        assert_tokens(
+            &DeviceRevision(0x00),
+            &[
+                Token::NewtypeStruct {
+                    name: "DeviceRevision",
+                },
+                Token::U8(0x00),
+            ],
+        );
+    }
+
+    #[test]
+    fn slave_address() {
+        assert_tokens(&SlaveAddress::Default, &[Token::U8(0x18)]);
+        assert_tokens(
+            &SlaveAddress::Alternative {
+                a2: false,
+                a1: false,
+                a0: true,
+            },
+            &[Token::U8(0x19)],
+        );
+        assert_de_tokens_error::<SlaveAddress>(
+            &[Token::U8(0x20)],
+            "invalid address, expected 0x18 to 0x1f",
+        );
+    }
+}
@@ -0,0 +1,190 @@
+#[cfg(all(test, feature = "embedded-storage"))]
+mod settings {
+    use embedded_storage::nor_flash::{ErrorType, NorFlash, NorFlashErrorKind, ReadNorFlash};
+    use mcp9808::{
+        calibration::LinearCalibration,
+        configuration::{Configuration, CriticalTemperatureLock, Hysteresis},
+        resolution::Resolution,
+        settings::{DecodeError, Error, SensorSettings, SIZE, VERSION},
+        simulator::Simulator,
+        temperature::Millicelsius,
+        SlaveAddress, MCP9808,
+    };
+
+    const CAPACITY: usize = 256;
+
+    struct MockFlash {
+        data: [u8; CAPACITY],
+        erase_count: u32,
+    }
+
+    impl MockFlash {
+        fn new() -> Self {
+            MockFlash {
+                data: [0xFF; CAPACITY],
+                erase_count: 0,
+            }
+        }
+
+        fn check(
+            &self,
+            offset: u32,
+            len: usize,
+            alignment: usize,
+        ) -> Result<(), NorFlashErrorKind> {
+            if offset as usize % alignment != 0 || len % alignment != 0 {
+                return Err(NorFlashErrorKind::NotAligned);
+            }
+            if offset as usize + len > CAPACITY {
+                return Err(NorFlashErrorKind::OutOfBounds);
+            }
+            Ok(())
+        }
+    }
+
+    impl ErrorType for MockFlash {
+        type Error = NorFlashErrorKind;
+    }
+
+    impl ReadNorFlash for MockFlash {
+        const READ_SIZE: usize = 4;
+
+        fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+            self.check(offset, bytes.len(), Self::READ_SIZE)?;
+            let offset = offset as usize;
+            bytes.copy_from_slice(&self.data[offset..offset + bytes.len()]);
+            Ok(())
+        }
+
+        fn capacity(&self) -> usize {
+            CAPACITY
+        }
+    }
+
+    impl NorFlash for MockFlash {
+        const WRITE_SIZE: usize = 4;
+        const ERASE_SIZE: usize = 64;
+
+        fn erase(&mut self, from: u32, to: u32) -> Result<(), Self::Error> {
+            self.check(from, (to - from) as usize, Self::ERASE_SIZE)?;
+            self.erase_count += 1;
+            self.data[from as usize..to as usize]
+                .iter_mut()
+                .for_each(|byte| *byte = 0xFF);
+            Ok(())
+        }
+
+        fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+            self.check(offset, bytes.len(), Self::WRITE_SIZE)?;
+            let offset = offset as usize;
+            self.data[offset..offset + bytes.len()]
+                .iter_mut()
+                .zip(bytes)
+                .for_each(|(stored, byte)| *stored &= byte);
+            Ok(())
+        }
+    }
+
+    fn settings() -> SensorSettings {
+        SensorSettings {
+            configuration: Configuration {
+                hysteresis: Hysteresis::Deg1_5C,
+                critical_temperature_lock: CriticalTemperatureLock::Locked,
+                ..Configuration::default()
+            },
+            upper_temperature: Millicelsius(40_250),
+            lower_temperature: Millicelsius(-10_500),
+            critical_temperature: Millicelsius(85_000),
+            resolution: Resolution::Deg0_25C,
+            calibration: LinearCalibration::new(Millicelsius(-300), 1_002_500).unwrap(),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let settings = settings();
+        let bytes = settings.to_bytes();
+        assert_eq!(b"M9", &bytes[0..2]);
+        assert_eq!(VERSION, bytes[2]);
+        assert_eq!(Ok(settings), SensorSettings::from_bytes(&bytes));
+        assert_eq!(
+            Ok(SensorSettings::default()),
+            SensorSettings::from_bytes(&SensorSettings::default().to_bytes())
+        );
+    }
+
+    #[test]
+    fn invalid_records() {
+        assert_eq!(
+            Err(DecodeError::Blank),
+            SensorSettings::from_bytes(&[0xFF; SIZE])
+        );
+
+        let mut bytes = settings().to_bytes();
+        bytes[0] = b'X';
+        assert_eq!(
+            Err(DecodeError::InvalidMagic),
+            SensorSettings::from_bytes(&bytes)
+        );
+
+        let mut bytes = settings().to_bytes();
+        bytes[2] = VERSION + 1;
+        assert_eq!(
+            Err(DecodeError::UnsupportedVersion(VERSION + 1)),
+            SensorSettings::from_bytes(&bytes)
+        );
+
+        let mut bytes = settings().to_bytes();
+        bytes[7] ^= 0x01;
+        assert_eq!(
+            Err(DecodeError::CrcMismatch),
+            SensorSettings::from_bytes(&bytes)
+        );
+    }
+
+    #[test]
+    fn save_and_load() {
+        let mut flash = MockFlash::new();
+        assert_eq!(
+            Err(Error::Decode(DecodeError::Blank)),
+            SensorSettings::load(&mut flash, 64)
+        );
+
+        SensorSettings::default().save(&mut flash, 64).unwrap();
+        settings().save(&mut flash, 64).unwrap();
+        assert_eq!(2, flash.erase_count);
+        assert_eq!(Ok(settings()), SensorSettings::load(&mut flash, 64));
+        assert!(flash.data[..64].iter().all(|&byte| byte == 0xFF));
+    }
+
+    #[test]
+    fn storage_errors() {
+        let mut flash = MockFlash::new();
+        assert_eq!(Err(Error::Misaligned), settings().save(&mut flash, 32));
+        assert_eq!(
+            Err(Error::Storage(NorFlashErrorKind::OutOfBounds)),
+            settings().save(&mut flash, 256)
+        );
+        assert_eq!(
+            Err(Error::Storage(NorFlashErrorKind::OutOfBounds)),
+            SensorSettings::load(&mut flash, 240)
+        );
+        assert_eq!(Err(Error::Misaligned), SensorSettings::load(&mut flash, 2));
+    }
+
+    #[test]
+    fn apply() {
+        let mut mcp9808 =
+            MCP9808::new(Simulator::new(SlaveAddress::Default), SlaveAddress::Default);
+        settings().apply(&mut mcp9808).unwrap();
+
+        assert_eq!(Ok(settings().configuration), mcp9808.read_configuration());
+        assert_eq!(Ok(Millicelsius(40_250)), mcp9808.read_upper_temperature());
+        assert_eq!(Ok(Millicelsius(-10_500)), mcp9808.read_lower_temperature());
+        assert_eq!(
+            Ok(Millicelsius(85_000)),
+            mcp9808.read_critical_temperature()
+        );
+        assert_eq!(Ok(Resolution::Deg0_25C), mcp9808.read_resolution());
+    }
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod statistics {
+    use mcp9808::{
+        statistics::{Extreme, TemperatureStats},
+        temperature::{Celsius, Millicelsius},
+    };
+
+    #[test]
+    fn empty() {
+        let stats = TemperatureStats::new();
+        assert_eq!(0, stats.count());
+        assert_eq!(None, stats.min());
+        assert_eq!(None, stats.max());
+        assert_eq!(None, stats.mean());
+        assert_eq!(None, stats.variance());
+        assert_eq!(None, stats.sample_variance());
+    }
+
+    #[test]
+    fn summary() {
+        let mut stats = TemperatureStats::new();
+        let samples = [2_000, 4_000, 4_000, 4_000, 5_000, 5_000, 7_000, 9_000];
+        for (index, sample) in samples.iter().enumerate() {
+            stats.add(Millicelsius(*sample), index as u64 * 100);
+        }
+
+        assert_eq!(8, stats.count());
+        assert_eq!(Some(Millicelsius(5_000)), stats.mean());
+        assert_eq!(Some(4_000_000), stats.variance());
+        assert_eq!(Some(4_571_429), stats.sample_variance());
+        assert_eq!(Some(Millicelsius(2_000)), stats.standard_deviation());
+        assert_eq!(
+            Some(Extreme {
+                temperature: Millicelsius(2_000),
+                timestamp_ms: 0
+            }),
+            stats.min()
+        );
+        assert_eq!(
+            Some(Extreme {
+                temperature: Millicelsius(9_000),
+                timestamp_ms: 700
+            }),
+            stats.max()
+        );
+    }
+
+    #[test]
+    fn first_extreme_wins() {
+        let mut stats = TemperatureStats::new();
+        stats.add(Celsius(21.5), 10);
+        stats.add(Celsius(21.5), 20);
+        assert_eq!(Some(10), stats.min().map(|min| min.timestamp_ms));
+        assert_eq!(Some(10), stats.max().map(|max| max.timestamp_ms));
+        assert_eq!(Some(0), stats.variance());
+    }
+
+    #[test]
+    fn negative_temperatures() {
+        let mut stats = TemperatureStats::new();
+        stats.add(Millicelsius(-24_063), 0);
+        stats.add(Millicelsius(-24_125), 1);
+        stats.add(Millicelsius(-24_000), 2);
+        assert_eq!(Some(Millicelsius(-24_063)), stats.mean());
+        assert_eq!(
+            Some(Millicelsius(-24_125)),
+            stats.min().map(|min| min.temperature)
+        );
+        assert_eq!(Some(2_604), stats.variance());
+    }
+
+    #[test]
+    fn reset_starts_new_period() {
+        let mut stats = TemperatureStats::new();
+        stats.add(Millicelsius(30_000), 0);
+        stats.reset();
+        stats.add(Millicelsius(20_000), 1_000);
+        assert_eq!(1, stats.count());
+        assert_eq!(Some(Millicelsius(20_000)), stats.mean());
+        assert_eq!(Some(1_000), stats.max().map(|max| max.timestamp_ms));
+    }
+}
@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod supervisor {
+    use mcp9808::{
+        configuration::{AlertOutputControl, Configuration, CriticalTemperatureLock, Hysteresis},
+        resolution::Resolution,
+        simulator::Simulator,
+        supervisor::{DesiredState, Status, Supervisor},
+        temperature::{Celsius, Millicelsius},
+        SlaveAddress, MCP9808,
+    };
+
+    fn desired_state() -> DesiredState {
+        DesiredState {
+            configuration: Configuration {
+                hysteresis: Hysteresis::Deg1_5C,
+                alert_output_control: AlertOutputControl::Enabled,
+                critical_temperature_lock: CriticalTemperatureLock::Locked,
+                ..Configuration::default()
+            },
+            upper_temperature: Millicelsius(60_000),
+            lower_temperature: Millicelsius(-10_125),
+            critical_temperature: Millicelsius(85_062),
+            resolution: Resolution::Deg0_125C,
+        }
+    }
+
+    #[test]
+    fn apply_desired_state() {
+        let mut mcp9808 =
+            MCP9808::new(Simulator::new(SlaveAddress::Default), SlaveAddress::Default);
+        let supervisor = Supervisor::new(desired_state());
+        supervisor.apply(&mut mcp9808).unwrap();
+
+        assert_eq!(
+            desired_state().configuration,
+            mcp9808.read_configuration().unwrap()
+        );
+        assert_eq!(Ok(Resolution::Deg0_125C), mcp9808.read_resolution());
+        assert_eq!(Ok(Celsius(60.0)), mcp9808.read_upper_temperature());
+        assert_eq!(Ok(Millicelsius(-10_250)), mcp9808.read_lower_temperature());
+        assert_eq!(
+            Ok(Millicelsius(85_000)),
+            mcp9808.read_critical_temperature()
+        );
+    }
+
+    #[test]
+    fn in_sync_after_apply() {
+        let mut mcp9808 =
+            MCP9808::new(Simulator::new(SlaveAddress::Default), SlaveAddress::Default);
+        let mut supervisor = Supervisor::new(desired_state());
+        supervisor.apply(&mut mcp9808).unwrap();
+
+        assert_eq!(Ok(Status::InSync), supervisor.check(&mut mcp9808));
+        assert_eq!(0, supervisor.reset_count());
+        assert_eq!(0, supervisor.mismatch_count());
+    }
+
+    #[test]
+    fn reset_is_detected_and_state_reapplied() {
+        let mut mcp9808 =
+            MCP9808::new(Simulator::new(SlaveAddress::Default), SlaveAddress::Default);
+        let mut supervisor = Supervisor::new(desired_state());
+        supervisor.apply(&mut mcp9808).unwrap();
+
+        for _ in 0..2 {
+            let mut simulator = mcp9808.release();
+            simulator.power_on_reset();
+            mcp9808 = MCP9808::new(simulator, SlaveAddress::Default);
+
+            assert_eq!(Ok(Status::ResetDetected), supervisor.check(&mut mcp9808));
+            assert_eq!(Ok(Status::InSync), supervisor.check(&mut mcp9808));
+        }
+        assert_eq!(2, supervisor.reset_count());
+        assert_eq!(Ok(Resolution::Deg0_125C), mcp9808.read_resolution());
+    }
+
+    #[test]
+    fn mismatch_is_reapplied() {
+        let mut mcp9808 =
+            MCP9808::new(Simulator::new(SlaveAddress::Default), SlaveAddress::Default);
+        let mut supervisor = Supervisor::new(desired_state());
+        supervisor.apply(&mut mcp9808).unwrap();
+
+        mcp9808.write_resolution(Resolution::Deg0_5C).unwrap();
+        assert_eq!(Ok(Status::Mismatch), supervisor.check(&mut mcp9808));
+        assert_eq!(Ok(Status::InSync), supervisor.check(&mut mcp9808));
+        assert_eq!(0, supervisor.reset_count());
+        assert_eq!(1, supervisor.mismatch_count());
+    }
+}
@@ -7,8 +7,8 @@ mod temperature {
 
         #[test]
         fn celsius_to_millicelsius() {
-            assert_eq!(Celsius(25.367), Millicelsius(25_367).into());
-            assert_eq!(Celsius(-0.789), Millicelsius(-789).into());
+            assert_eq!(Celsius(25.367), Celsius::from(Millicelsius(25_367)));
+            assert_eq!(Celsius(-0.789), Celsius::from(Millicelsius(-789)));
         }
 
         mod temperature_to_raw {
@@ -114,95 +114,365 @@ mod temperature {
 
             #[test]
             fn zero_millicelsius() {
-                assert_eq!(Millicelsius(0), arr![u8; 0b0000_0000, 0b0000_0000].into());
+                assert_eq!(
+                    Millicelsius(0),
+                    Millicelsius::from(arr![u8; 0b0000_0000, 0b0000_0000])
+                );
             }
 
             #[test]
             fn zero_celsius() {
-                assert_eq!(Celsius(0.0), arr![u8; 0b0000_0000, 0b0000_0000].into());
+                assert_eq!(
+                    Celsius(0.0),
+                    Celsius::from(arr![u8; 0b0000_0000, 0b0000_0000])
+                );
             }
 
             #[test]
             fn slightly_above_zero_millicelsius() {
-                assert_eq!(Millicelsius(62), arr![u8; 0b0000_0000, 0b0000_0001].into());
-                assert_eq!(Millicelsius(125), arr![u8; 0b0000_0000, 0b0000_0010].into());
-                assert_eq!(Millicelsius(500), arr![u8; 0b0000_0000, 0b0000_1000].into());
-                assert_eq!(Millicelsius(625), arr![u8; 0b0000_0000, 0b0000_1010].into());
+                assert_eq!(
+                    Millicelsius(62),
+                    Millicelsius::from(arr![u8; 0b0000_0000, 0b0000_0001])
+                );
+                assert_eq!(
+                    Millicelsius(125),
+                    Millicelsius::from(arr![u8; 0b0000_0000, 0b0000_0010])
+                );
+                assert_eq!(
+                    Millicelsius(500),
+                    Millicelsius::from(arr![u8; 0b0000_0000, 0b0000_1000])
+                );
+                assert_eq!(
+                    Millicelsius(625),
+                    Millicelsius::from(arr![u8; 0b0000_0000, 0b0000_1010])
+                );
             }
 
             #[test]
             fn slightly_above_zero_celsius() {
-                assert_eq!(Celsius(0.062), arr![u8; 0b0000_0000, 0b0000_0001].into());
-                assert_eq!(Celsius(0.125), arr![u8; 0b0000_0000, 0b0000_0010].into());
-                assert_eq!(Celsius(0.500), arr![u8; 0b0000_0000, 0b0000_1000].into());
-                assert_eq!(Celsius(0.625), arr![u8; 0b0000_0000, 0b0000_1010].into());
+                assert_eq!(
+                    Celsius(0.062),
+                    Celsius::from(arr![u8; 0b0000_0000, 0b0000_0001])
+                );
+                assert_eq!(
+                    Celsius(0.125),
+                    Celsius::from(arr![u8; 0b0000_0000, 0b0000_0010])
+                );
+                assert_eq!(
+                    Celsius(0.500),
+                    Celsius::from(arr![u8; 0b0000_0000, 0b0000_1000])
+                );
+                assert_eq!(
+                    Celsius(0.625),
+                    Celsius::from(arr![u8; 0b0000_0000, 0b0000_1010])
+                );
             }
 
             #[test]
             fn slightly_below_zero_millicelsius() {
-                assert_eq!(Millicelsius(-63), arr![u8; 0b0001_1111, 0b1111_1111].into());
+                assert_eq!(
+                    Millicelsius(-63),
+                    Millicelsius::from(arr![u8; 0b0001_1111, 0b1111_1111])
+                );
                 assert_eq!(
                     Millicelsius(-125),
-                    arr![u8; 0b0001_1111, 0b1111_1110].into()
+                    Millicelsius::from(arr![u8; 0b0001_1111, 0b1111_1110])
                 );
                 assert_eq!(
                     Millicelsius(-250),
-                    arr![u8; 0b0001_1111, 0b1111_1100].into()
+                    Millicelsius::from(arr![u8; 0b0001_1111, 0b1111_1100])
                 );
                 assert_eq!(
                     Millicelsius(-563),
-                    arr![u8; 0b0001_1111, 0b1111_0111].into()
+                    Millicelsius::from(arr![u8; 0b0001_1111, 0b1111_0111])
                 );
                 assert_eq!(
                     Millicelsius(-688),
-                    arr![u8; 0b0001_1111, 0b1111_0101].into()
+                    Millicelsius::from(arr![u8; 0b0001_1111, 0b1111_0101])
                 );
             }
 
             #[test]
             fn slightly_below_zero_celsius() {
-                assert_eq!(Celsius(-0.063), arr![u8; 0b0001_1111, 0b1111_1111].into());
-                assert_eq!(Celsius(-0.125), arr![u8; 0b0001_1111, 0b1111_1110].into());
-                assert_eq!(Celsius(-0.250), arr![u8; 0b0001_1111, 0b1111_1100].into());
-                assert_eq!(Celsius(-0.563), arr![u8; 0b0001_1111, 0b1111_0111].into());
-                assert_eq!(Celsius(-0.688), arr![u8; 0b0001_1111, 0b1111_0101].into());
+                assert_eq!(
+                    Celsius(-0.063),
+                    Celsius::from(arr![u8; 0b0001_1111, 0b1111_1111])
+                );
+                assert_eq!(
+                    Celsius(-0.125),
+                    Celsius::from(arr![u8; 0b0001_1111, 0b1111_1110])
+                );
+                assert_eq!(
+                    Celsius(-0.250),
+                    Celsius::from(arr![u8; 0b0001_1111, 0b1111_1100])
+                );
+                assert_eq!(
+                    Celsius(-0.563),
+                    Celsius::from(arr![u8; 0b0001_1111, 0b1111_0111])
+                );
+                assert_eq!(
+                    Celsius(-0.688),
+                    Celsius::from(arr![u8; 0b0001_1111, 0b1111_0101])
+                );
             }
 
             #[test]
             fn above_zero_millicelsius() {
                 assert_eq!(
                     Millicelsius(25250),
-                    arr![u8; 0b0000_00001, 0b1001_0100].into()
+                    Millicelsius::from(arr![u8; 0b0000_00001, 0b1001_0100])
                 );
                 assert_eq!(
                     Millicelsius(57375),
-                    arr![u8; 0b0000_00011, 0b1001_0110].into()
+                    Millicelsius::from(arr![u8; 0b0000_00011, 0b1001_0110])
                 );
             }
 
             #[test]
             fn above_zero_celsius() {
-                assert_eq!(Celsius(25.250), arr![u8; 0b0000_00001, 0b1001_0100].into());
-                assert_eq!(Celsius(57.375), arr![u8; 0b0000_00011, 0b1001_0110].into());
+                assert_eq!(
+                    Celsius(25.250),
+                    Celsius::from(arr![u8; 0b0000_00001, 0b1001_0100])
+                );
+                assert_eq!(
+                    Celsius(57.375),
+                    Celsius::from(arr![u8; 0b0000_00011, 0b1001_0110])
+                );
             }
 
             #[test]
             fn below_zero_millicelsius() {
                 assert_eq!(
                     Millicelsius(-24063),
-                    arr![u8; 0b0000_11110, 0b0111_1111].into()
+                    Millicelsius::from(arr![u8; 0b0000_11110, 0b0111_1111])
                 );
                 assert_eq!(
                     Millicelsius(-56375),
-                    arr![u8; 0b0000_11100, 0b0111_1010].into()
+                    Millicelsius::from(arr![u8; 0b0000_11100, 0b0111_1010])
                 );
             }
 
             #[test]
             fn below_zero_celsius() {
-                assert_eq!(Celsius(-24.063), arr![u8; 0b0000_11110, 0b0111_1111].into());
-                assert_eq!(Celsius(-56.375), arr![u8; 0b0000_11100, 0b0111_1010].into());
+                assert_eq!(
+                    Celsius(-24.063),
+                    Celsius::from(arr![u8; 0b0000_11110, 0b0111_1111])
+                );
+                assert_eq!(
+                    Celsius(-56.375),
+                    Celsius::from(arr![u8; 0b0000_11100, 0b0111_1010])
+                );
             }
         }
     }
+
+    mod display {
+        use mcp9808::{
+            resolution::Resolution,
+            temperature::{Celsius, Millicelsius},
+        };
+
+        #[test]
+        fn temperatures() {
+            assert_eq!("-24.063 °C", Celsius(-24.063).to_string());
+            assert_eq!("-24.1 °C", format!("{:.1}", Celsius(-24.063)));
+            assert_eq!("-24063 m°C", Millicelsius(-24_063).to_string());
+        }
+
+        #[test]
+        fn resolution_precision() {
+            let temperature = Millicelsius(-24_063);
+            assert_eq!(
+                "-24.0 °C",
+                Resolution::Deg0_5C.display(temperature).to_string()
+            );
+            assert_eq!(
+                "-24.00 °C",
+                Resolution::Deg0_25C.display(temperature).to_string()
+            );
+            assert_eq!(
+                "-24.125 °C",
+                Resolution::Deg0_125C
+                    .display(Millicelsius(-24_125))
+                    .to_string()
+            );
+            assert_eq!(
+                "-24.0625 °C",
+                Resolution::Deg0_0625C.display(temperature).to_string()
+            );
+            assert_eq!(
+                "57.3750 °C",
+                Resolution::Deg0_0625C.display(Celsius(57.375)).to_string()
+            );
+            assert_eq!(
+                "-0.0625 °C",
+                Resolution::Deg0_0625C
+                    .display(Millicelsius(-62))
+                    .to_string()
+            );
+            assert_eq!(
+                "0.5 °C",
+                Resolution::Deg0_5C.display(Millicelsius(250)).to_string()
+            );
+        }
+    }
+
+    mod parse {
+        use mcp9808::temperature::{Celsius, Millicelsius, ParseTemperatureError};
+
+        #[test]
+        fn millicelsius() {
+            assert_eq!(Ok(Millicelsius(-24_063)), "-24063 m°C".parse());
+            assert_eq!(Ok(Millicelsius(-24_063)), "-24063mC".parse());
+            assert_eq!(Ok(Millicelsius(24_063)), "24063".parse());
+            assert_eq!(Ok(Millicelsius(-24_063)), "-24.0625 °C".parse());
+            assert_eq!(Ok(Millicelsius(25_250)), " +25.25C ".parse());
+            assert_eq!(Ok(Millicelsius(1)), "0.5 m°C".parse());
+        }
+
+        #[test]
+        fn celsius() {
+            assert_eq!(Ok(Celsius(-24.0625)), "-24.0625 °C".parse());
+            assert_eq!(Ok(Celsius(21.5)), "21.5C".parse());
+            assert_eq!(Ok(Celsius(21.5)), "21.5".parse());
+            assert_eq!(Ok(Celsius(-3.25)), "-3250 m°C".parse());
+        }
+
+        #[test]
+        fn invalid() {
+            for text in &[
+                "", "°C", "warm", "21,5 °C", "21.5 °F", "inf", "NaN C", "--1", "1e3",
+            ] {
+                assert_eq!(
+                    Err(ParseTemperatureError),
+                    text.parse::<Millicelsius>(),
+                    "{}",
+                    text
+                );
+                assert_eq!(
+                    Err(ParseTemperatureError),
+                    text.parse::<Celsius>(),
+                    "{}",
+                    text
+                );
+            }
+            assert_eq!(
+                Err(ParseTemperatureError),
+                "3000000 °C".parse::<Millicelsius>()
+            );
+        }
+    }
+
+    mod arithmetic {
+        use mcp9808::temperature::{Celsius, Millicelsius, TemperatureDelta};
+
+        #[test]
+        fn delta_between_temperatures() {
+            assert_eq!(
+                TemperatureDelta(1_250),
+                Millicelsius(25_250) - Millicelsius(24_000)
+            );
+            assert_eq!(
+                TemperatureDelta(-24_063),
+                Millicelsius(-24_063) - Millicelsius(0)
+            );
+            assert_eq!(TemperatureDelta(-3_250), Celsius(18.0) - Celsius(21.25));
+            assert_eq!(TemperatureDelta(63), Celsius(0.0625) - Celsius(0.0));
+            assert_eq!(TemperatureDelta(500), Millicelsius(21_000) - Celsius(20.5));
+            assert_eq!(TemperatureDelta(-500), Celsius(20.5) - Millicelsius(21_000));
+        }
+
+        #[test]
+        fn offset_by_delta() {
+            let mut temperature = Millicelsius(21_000) + TemperatureDelta(1_500);
+            assert_eq!(Millicelsius(22_500), temperature);
+            temperature -= TemperatureDelta(3_000);
+            assert_eq!(Millicelsius(19_500), temperature);
+            temperature += -TemperatureDelta(500);
+            assert_eq!(Millicelsius(19_000), temperature);
+
+            let mut celsius = Celsius(21.0) - TemperatureDelta(250);
+            assert_eq!(Celsius(20.75), celsius);
+            celsius += TemperatureDelta(1_250);
+            assert_eq!(Celsius(22.0), celsius);
+
+            let mut delta = TemperatureDelta(100) + TemperatureDelta(50);
+            delta -= TemperatureDelta(200);
+            assert_eq!(TemperatureDelta(-50), delta);
+            assert_eq!(TemperatureDelta(50), delta.abs());
+        }
+
+        #[test]
+        fn ordering() {
+            let mut readings = [Millicelsius(25_000), Millicelsius(-40), Millicelsius(0)];
+            readings.sort();
+            assert_eq!(
+                [Millicelsius(-40), Millicelsius(0), Millicelsius(25_000)],
+                readings
+            );
+            assert_eq!(Some(&Millicelsius(25_000)), readings.iter().max());
+            assert!(TemperatureDelta(-1) < TemperatureDelta::default());
+            assert!((Millicelsius(21_000) - Millicelsius(20_000)).abs() <= TemperatureDelta(1_000));
+        }
+
+        #[test]
+        fn ordering_across_units() {
+            assert!(Millicelsius(21_500) < Celsius(22.0));
+            assert!(Celsius(22.0) > Millicelsius(21_500));
+            assert!(Millicelsius(-3_250) >= Celsius(-3.25));
+            assert!(Celsius(-3.5) <= Millicelsius(-3_250));
+            assert_eq!(Millicelsius(24_063), Celsius(24.0625));
+            assert_eq!(Celsius(0.0004), Millicelsius(0));
+            assert_ne!(Millicelsius(1), Celsius(0.0));
+            assert_ne!(Millicelsius(0), Celsius(f32::NAN));
+            assert_eq!(None, Celsius(f32::NAN).partial_cmp(&Millicelsius(0)));
+        }
+
+        #[test]
+        fn checked_and_saturating() {
+            let max = Millicelsius(i32::MAX);
+            let min = Millicelsius(i32::MIN);
+
+            assert_eq!(None, max.checked_add(TemperatureDelta(1)));
+            assert_eq!(None, min.checked_sub(TemperatureDelta(1)));
+            assert_eq!(
+                Some(Millicelsius(1_000)),
+                Millicelsius(500).checked_add(TemperatureDelta(500))
+            );
+            assert_eq!(
+                Some(Millicelsius(0)),
+                Millicelsius(500).checked_sub(TemperatureDelta(500))
+            );
+            assert_eq!(max, max.saturating_add(TemperatureDelta(1)));
+            assert_eq!(min, min.saturating_sub(TemperatureDelta(1)));
+
+            assert_eq!(None, max.checked_delta(min));
+            assert_eq!(
+                Some(TemperatureDelta(-1_000)),
+                Millicelsius(0).checked_delta(Millicelsius(1_000))
+            );
+            assert_eq!(TemperatureDelta(i32::MAX), max.saturating_delta(min));
+            assert_eq!(TemperatureDelta(i32::MIN), min.saturating_delta(max));
+
+            let delta = TemperatureDelta(i32::MAX);
+            assert_eq!(None, delta.checked_add(TemperatureDelta(1)));
+            assert_eq!(
+                Some(TemperatureDelta(i32::MAX - 1)),
+                delta.checked_sub(TemperatureDelta(1))
+            );
+            assert_eq!(delta, delta.saturating_add(TemperatureDelta(1)));
+            assert_eq!(
+                TemperatureDelta(i32::MIN),
+                TemperatureDelta(i32::MIN).saturating_sub(TemperatureDelta(1))
+            );
+            assert_eq!(delta, TemperatureDelta(i32::MIN).abs());
+        }
+
+        #[test]
+        fn display() {
+            assert_eq!("+1250 m°C", TemperatureDelta(1_250).to_string());
+            assert_eq!("-63 m°C", TemperatureDelta(-63).to_string());
+            assert_eq!("+0 m°C", TemperatureDelta(0).to_string());
+        }
+    }
 }
@@ -0,0 +1,165 @@
+#[cfg(test)]
+mod thermostat {
+    use core::cell::Cell;
+    use core::convert::Infallible;
+    use embedded_hal::digital::v2::OutputPin;
+    use mcp9808::{
+        clock::Clock,
+        temperature::{Celsius, Millicelsius},
+        thermostat::{Output, Polarity, Thermostat},
+    };
+
+    #[derive(Default)]
+    struct MockPin {
+        high: Option<bool>,
+        writes: usize,
+    }
+
+    impl OutputPin for MockPin {
+        type Error = Infallible;
+
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.high = Some(false);
+            self.writes += 1;
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.high = Some(true);
+            self.writes += 1;
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockClock(Cell<u64>);
+
+    impl MockClock {
+        fn advance(&self, milliseconds: u64) {
+            self.0.set(self.0.get() + milliseconds);
+        }
+    }
+
+    impl Clock for MockClock {
+        fn now_ms(&self) -> u64 {
+            self.0.get()
+        }
+    }
+
+    #[test]
+    fn first_step_drives_output() {
+        let clock = MockClock::default();
+        let mut thermostat = Thermostat::new(
+            MockPin::default(),
+            &clock,
+            Millicelsius(20_000),
+            Millicelsius(1_000),
+            Polarity::Heating,
+        );
+        assert_eq!(None, thermostat.state());
+
+        let state = thermostat.update(Millicelsius(20_000)).unwrap();
+        assert_eq!(Output::Off, state.output);
+        assert!(!state.held);
+
+        let (pin, _) = thermostat.release();
+        assert_eq!(Some(false), pin.high);
+        assert_eq!(1, pin.writes);
+    }
+
+    #[test]
+    fn heating_with_hysteresis() {
+        let clock = MockClock::default();
+        let mut thermostat = Thermostat::new(
+            MockPin::default(),
+            &clock,
+            Celsius(20.0),
+            Celsius(1.0),
+            Polarity::Heating,
+        );
+
+        assert_eq!(Output::On, thermostat.update(Celsius(19.4)).unwrap().output);
+        assert_eq!(Output::On, thermostat.update(Celsius(20.2)).unwrap().output);
+        assert_eq!(
+            Output::Off,
+            thermostat.update(Celsius(20.5)).unwrap().output
+        );
+        assert_eq!(
+            Output::Off,
+            thermostat.update(Celsius(19.8)).unwrap().output
+        );
+        assert_eq!(Output::On, thermostat.update(Celsius(19.3)).unwrap().output);
+
+        let (pin, _) = thermostat.release();
+        assert_eq!(Some(true), pin.high);
+        assert_eq!(3, pin.writes);
+    }
+
+    #[test]
+    fn cooling_with_hysteresis() {
+        let clock = MockClock::default();
+        let mut thermostat = Thermostat::new(
+            MockPin::default(),
+            &clock,
+            Millicelsius(30_000),
+            Millicelsius(2_000),
+            Polarity::Cooling,
+        );
+
+        assert_eq!(
+            Output::Off,
+            thermostat.update(Millicelsius(30_500)).unwrap().output
+        );
+        assert_eq!(
+            Output::On,
+            thermostat.update(Millicelsius(31_063)).unwrap().output
+        );
+        assert_eq!(
+            Output::On,
+            thermostat.update(Millicelsius(29_125)).unwrap().output
+        );
+        assert_eq!(
+            Output::Off,
+            thermostat.update(Millicelsius(29_000)).unwrap().output
+        );
+    }
+
+    #[test]
+    fn minimum_on_and_off_time() {
+        let clock = MockClock::default();
+        let mut thermostat = Thermostat::new(
+            MockPin::default(),
+            &clock,
+            Millicelsius(20_000),
+            Millicelsius(0),
+            Polarity::Heating,
+        );
+        thermostat.set_min_on_time(10_000);
+        thermostat.set_min_off_time(5_000);
+
+        assert_eq!(
+            Output::Off,
+            thermostat.update(Millicelsius(21_000)).unwrap().output
+        );
+
+        clock.advance(1_000);
+        let state = thermostat.update(Millicelsius(19_000)).unwrap();
+        assert_eq!(Output::Off, state.output);
+        assert!(state.held);
+
+        clock.advance(4_000);
+        let state = thermostat.update(Millicelsius(19_000)).unwrap();
+        assert_eq!(Output::On, state.output);
+        assert!(!state.held);
+
+        clock.advance(9_999);
+        let state = thermostat.update(Millicelsius(21_000)).unwrap();
+        assert_eq!(Output::On, state.output);
+        assert!(state.held);
+
+        clock.advance(1);
+        let state = thermostat.update(Millicelsius(21_000)).unwrap();
+        assert_eq!(Output::Off, state.output);
+        assert_eq!(Some(state), thermostat.state());
+    }
+}
@@ -0,0 +1,151 @@
+#[cfg(test)]
+mod trace {
+    use i2c_interface::generic_array::typenum::consts::U3;
+    use mcp9808::{
+        configuration::{Configuration, Hysteresis},
+        device_id_revision::{DeviceId, DeviceRevision},
+        resolution::Resolution,
+        simulator::{self, Simulator},
+        temperature::{Millicelsius, TemperatureMeasurement},
+        trace::{Operation, RegisterValue, TraceBuffer, TracingI2c, Transaction},
+        SlaveAddress, MCP9808,
+    };
+
+    #[test]
+    fn records_transactions() {
+        let bus = TracingI2c::new(
+            Simulator::new(SlaveAddress::Default),
+            TraceBuffer::<U3>::new(),
+        );
+        let mut mcp9808 = MCP9808::new(bus, SlaveAddress::Default);
+        mcp9808
+            .write_upper_temperature(Millicelsius(20_000))
+            .unwrap();
+        mcp9808.read_upper_temperature::<Millicelsius, _>().unwrap();
+
+        let transactions: Vec<Transaction> = mcp9808.i2c().sink().iter().cloned().collect();
+        assert_eq!(
+            vec![
+                Transaction {
+                    operation: Operation::Write,
+                    address: 0x18,
+                    pointer: Some(0b0010),
+                    data: [0x01, 0x40],
+                    len: 2,
+                    ok: true,
+                },
+                Transaction {
+                    operation: Operation::Read,
+                    address: 0x18,
+                    pointer: Some(0b0010),
+                    data: [0x01, 0x40],
+                    len: 2,
+                    ok: true,
+                },
+            ],
+            transactions
+        );
+        assert_eq!(
+            "read 0x18 T_UPPER [01 40] ok UpperTemperature(Millicelsius(20000))",
+            transactions[1].to_string()
+        );
+    }
+
+    #[test]
+    fn decodes_registers() {
+        let mut simulator = Simulator::new(SlaveAddress::Default);
+        simulator.set_ambient_temperature(Millicelsius(25_250));
+        let bus = TracingI2c::new(simulator, TraceBuffer::<U3>::new());
+        let mut mcp9808 = MCP9808::new(bus, SlaveAddress::Default);
+
+        let configuration = Configuration {
+            hysteresis: Hysteresis::Deg6_0C,
+            ..Configuration::default()
+        };
+        mcp9808.write_configuration(configuration).unwrap();
+        mcp9808.read_device_information().unwrap();
+        let _: TemperatureMeasurement<Millicelsius> = mcp9808.read_ambient_temperature().unwrap();
+
+        let values: Vec<Option<RegisterValue>> = mcp9808
+            .i2c()
+            .sink()
+            .iter()
+            .map(Transaction::decode)
+            .collect();
+        assert_eq!(
+            vec![
+                Some(RegisterValue::Configuration(configuration)),
+                Some(RegisterValue::DeviceInformation(
+                    DeviceId(0x04),
+                    DeviceRevision(0x00)
+                )),
+                Some(RegisterValue::AmbientTemperature(TemperatureMeasurement {
+                    temperature: Millicelsius(25_250),
+                    is_critical: true,
+                    is_upper: true,
+                    is_lower: false,
+                })),
+            ],
+            values
+        );
+    }
+
+    #[test]
+    fn ring_buffer_keeps_latest() {
+        let bus = TracingI2c::new(
+            Simulator::new(SlaveAddress::Default),
+            TraceBuffer::<U3>::new(),
+        );
+        let mut mcp9808 = MCP9808::new(bus, SlaveAddress::Default);
+        for resolution in &[
+            Resolution::Deg0_5C,
+            Resolution::Deg0_25C,
+            Resolution::Deg0_125C,
+            Resolution::Deg0_0625C,
+            Resolution::Deg0_5C,
+        ] {
+            mcp9808.write_resolution(*resolution).unwrap();
+        }
+
+        let buffer = mcp9808.i2c().sink();
+        assert_eq!(3, buffer.len());
+        assert_eq!(2, buffer.dropped());
+        let lines: Vec<String> = buffer.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            vec![
+                "write 0x18 RESOLUTION [02] ok Resolution(Deg0_125C)",
+                "write 0x18 RESOLUTION [03] ok Resolution(Deg0_0625C)",
+                "write 0x18 RESOLUTION [00] ok Resolution(Deg0_5C)",
+            ],
+            lines
+        );
+
+        let (_, mut buffer) = mcp9808.release().release();
+        buffer.clear();
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn callback_sink_records_errors() {
+        let mut lines = Vec::new();
+        {
+            let bus = TracingI2c::new(
+                Simulator::new(SlaveAddress::Default),
+                |transaction: Transaction| lines.push(transaction.to_string()),
+            );
+            let mut mcp9808 = MCP9808::new(
+                bus,
+                SlaveAddress::Alternative {
+                    a2: false,
+                    a1: false,
+                    a0: true,
+                },
+            );
+            assert_eq!(
+                Err(simulator::Error::AddressNack),
+                mcp9808.read_manufacturer_id()
+            );
+        }
+        assert_eq!(vec!["read 0x19 MANUFACTURER_ID [] error"], lines);
+    }
+}
@@ -0,0 +1,71 @@
+#[cfg(all(test, feature = "uom"))]
+mod uom {
+    use mcp9808::{
+        simulator::Simulator,
+        temperature::{Millicelsius, TemperatureMeasurement},
+        SlaveAddress, MCP9808,
+    };
+    use uom::si::{
+        f32::ThermodynamicTemperature,
+        thermodynamic_temperature::{degree_celsius, kelvin},
+    };
+
+    fn celsius(value: f32) -> ThermodynamicTemperature {
+        ThermodynamicTemperature::new::<degree_celsius>(value)
+    }
+
+    #[test]
+    fn conversions() {
+        for &millicelsius in &[0, 62, -62, 125_000, -40_000, 255_937, -256_000, -24_063] {
+            let temperature = ThermodynamicTemperature::from(Millicelsius(millicelsius));
+            assert_eq!(Millicelsius(millicelsius), Millicelsius::from(temperature));
+        }
+        assert_eq!(
+            Millicelsius(-273_150),
+            Millicelsius::from(ThermodynamicTemperature::new::<kelvin>(0.0))
+        );
+        assert_eq!(
+            Millicelsius(26_850),
+            Millicelsius::from(ThermodynamicTemperature::new::<kelvin>(300.0))
+        );
+        assert_eq!(Millicelsius(i32::MAX), Millicelsius::from(celsius(1.0e9)));
+    }
+
+    #[test]
+    fn read_ambient_temperature() {
+        // Register steps are 0.0625 °C, readings pass through whole Millicelsius
+        let mut simulator = Simulator::new(SlaveAddress::Default);
+        for &value in &[125.0, -40.0, 0.0, 0.0625, -0.0625, 255.9375, -256.0] {
+            simulator.set_ambient_temperature(celsius(value));
+            let mut mcp9808 = MCP9808::new(simulator, SlaveAddress::Default);
+
+            let measurement: TemperatureMeasurement<ThermodynamicTemperature> =
+                mcp9808.read_ambient_temperature().unwrap();
+
+            let error = measurement.temperature.get::<degree_celsius>() - value;
+            assert!(error.abs() <= 0.001, "{} read as {:?}", value, error);
+            simulator = mcp9808.release();
+        }
+    }
+
+    #[test]
+    fn write_and_read_limits() {
+        let mut mcp9808 =
+            MCP9808::new(Simulator::new(SlaveAddress::Default), SlaveAddress::Default);
+
+        mcp9808.write_upper_temperature(celsius(125.0)).unwrap();
+        mcp9808.write_lower_temperature(celsius(-40.0)).unwrap();
+        mcp9808.write_critical_temperature(celsius(-0.25)).unwrap();
+
+        let upper: ThermodynamicTemperature = mcp9808.read_upper_temperature().unwrap();
+        let lower: ThermodynamicTemperature = mcp9808.read_lower_temperature().unwrap();
+        let critical: ThermodynamicTemperature = mcp9808.read_critical_temperature().unwrap();
+        assert_eq!(Millicelsius(125_000), Millicelsius::from(upper));
+        assert_eq!(Millicelsius(-40_000), Millicelsius::from(lower));
+        assert_eq!(Millicelsius(-250), Millicelsius::from(critical));
+        assert_eq!(
+            Millicelsius(125_000),
+            mcp9808.read_upper_temperature::<Millicelsius, _>().unwrap()
+        );
+    }
+}
@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod verify {
+    use mcp9808::{
+        configuration::{
+            Configuration, CriticalTemperatureLock, Hysteresis, InterruptClear, ShutdownMode,
+            TemperatureWindowLock,
+        },
+        resolution::Resolution,
+        simulator::{self, Simulator},
+        temperature::{Celsius, Millicelsius},
+        verify::Error,
+        Register, SlaveAddress, MCP9808,
+    };
+
+    fn mcp9808() -> MCP9808<Simulator> {
+        let mut mcp9808 =
+            MCP9808::new(Simulator::new(SlaveAddress::Default), SlaveAddress::Default);
+        mcp9808.enable_write_verification();
+        mcp9808
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let mut mcp9808 =
+            MCP9808::new(Simulator::new(SlaveAddress::Default), SlaveAddress::Default);
+        assert!(!mcp9808.is_write_verification_enabled());
+        mcp9808
+            .write_configuration(Configuration {
+                critical_temperature_lock: CriticalTemperatureLock::Locked,
+                ..Configuration::default()
+            })
+            .unwrap();
+
+        assert_eq!(
+            Ok(()),
+            mcp9808.write_critical_temperature(Millicelsius(100_000))
+        );
+        assert_eq!(Ok(Millicelsius(0)), mcp9808.read_critical_temperature());
+
+        mcp9808.enable_write_verification();
+        assert!(mcp9808.is_write_verification_enabled());
+        assert!(mcp9808
+            .write_critical_temperature(Millicelsius(100_000))
+            .is_err());
+        mcp9808.disable_write_verification();
+        assert!(!mcp9808.is_write_verification_enabled());
+    }
+
+    #[test]
+    fn unlocked_writes() {
+        let mut mcp9808 = mcp9808();
+        let configuration = Configuration {
+            hysteresis: Hysteresis::Deg3_0C,
+            shutdown_mode: ShutdownMode::Shutdown,
+            interrupt_clear: InterruptClear::Cleared,
+            ..Configuration::default()
+        };
+
+        assert_eq!(Ok(()), mcp9808.write_configuration(configuration));
+        assert_eq!(Ok(()), mcp9808.write_upper_temperature(Celsius(45.3)));
+        assert_eq!(
+            Ok(()),
+            mcp9808.write_lower_temperature(Millicelsius(-5_000))
+        );
+        assert_eq!(
+            Ok(()),
+            mcp9808.write_critical_temperature(Millicelsius(90_125))
+        );
+        assert_eq!(Ok(()), mcp9808.write_resolution(Resolution::Deg0_5C));
+        assert_eq!(Ok(Millicelsius(45_250)), mcp9808.read_upper_temperature());
+    }
+
+    #[test]
+    fn critical_lock() {
+        let mut mcp9808 = mcp9808();
+        mcp9808
+            .write_critical_temperature(Millicelsius(80_000))
+            .unwrap();
+        mcp9808
+            .write_configuration(Configuration {
+                critical_temperature_lock: CriticalTemperatureLock::Locked,
+                ..Configuration::default()
+            })
+            .unwrap();
+
+        assert_eq!(
+            Err(Error::VerifyFailed {
+                register: Register::CriticalTemperatureRegister,
+                expected: 0x0640,
+                actual: 0x0500,
+            }),
+            mcp9808.write_critical_temperature(Millicelsius(100_000))
+        );
+        assert_eq!(
+            Ok(()),
+            mcp9808.write_upper_temperature(Millicelsius(60_000))
+        );
+    }
+
+    #[test]
+    fn window_lock() {
+        let mut mcp9808 = mcp9808();
+        let locked = Configuration {
+            hysteresis: Hysteresis::Deg1_5C,
+            temperature_window_lock: TemperatureWindowLock::Locked,
+            ..Configuration::default()
+        };
+        mcp9808.write_configuration(locked).unwrap();
+
+        assert_eq!(
+            Err(Error::VerifyFailed {
+                register: Register::LowerTemperatureRegister,
+                expected: 0x1f60,
+                actual: 0x0000,
+            }),
+            mcp9808.write_lower_temperature(Millicelsius(-10_000))
+        );
+        assert_eq!(
+            Err(Error::VerifyFailed {
+                register: Register::ConfigurationRegister,
+                expected: 0x0000,
+                actual: 0x0240,
+            }),
+            mcp9808.write_configuration(Configuration::default())
+        );
+    }
+
+    #[test]
+    fn bus_error() {
+        let mut mcp9808 = MCP9808::new(
+            Simulator::new(SlaveAddress::Default),
+            SlaveAddress::Alternative {
+                a2: true,
+                a1: true,
+                a0: true,
+            },
+        );
+        mcp9808.enable_write_verification();
+        assert_eq!(
+            Err(Error::I2c(simulator::Error::AddressNack)),
+            mcp9808.write_resolution(Resolution::Deg0_25C)
+        );
+    }
+}